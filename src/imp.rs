@@ -1,4 +1,43 @@
+use std::fmt;
 use std::marker::PhantomData;
+use std::mem;
+use std::panic::Location;
+use std::process;
+use std::ptr;
+
+mod buildable;
+mod concurrent;
+mod context;
+mod dyn_builder;
+mod eager;
+mod errors;
+mod in_place;
+pub mod ops;
+
+#[cfg(feature = "log")]
+mod logging;
+
+mod drop_check;
+
+mod unwind;
+
+pub use self::buildable::Buildable;
+pub use self::concurrent::ConcurrentFluentBuilder;
+pub use self::dyn_builder::{AnyFluentBuilder, DynBuilder};
+pub use self::errors::{BuildError, BuildErrors, BuildPath, BuildPathRef};
+pub use self::unwind::StepPanic;
+
+use self::drop_check::DropCheck;
+use self::eager::Immediate;
+
+#[cfg(feature = "serde")]
+mod serde_support;
+
+#[cfg(feature = "json_patch")]
+mod json_patch;
+
+#[cfg(feature = "json_patch")]
+pub use self::json_patch::{PatchContext, PatchError};
 
 /**
 Indicate that fluent methods should be stacked on top of eachother.
@@ -10,6 +49,192 @@ Indicate that fluent methods should override eachother.
 */
 pub enum Override {}
 
+/**
+Indicate that fluent methods should be applied immediately when the builder contains a value.
+
+Fluent methods are only stacked while the builder is waiting on a default value, or if they need a context.
+*/
+pub enum Eager {}
+
+/**
+A way to combine the fluent methods added to a `FluentBuilder`.
+
+When a fluent method is added to a builder, its stack mode decides which of the builder's
+current value and fluent method are kept underneath the new one,
+and whether the new method is applied to the value straight away instead of being stored.
+This means code can be written over any stack mode, and new modes can be added outside of this crate.
+
+# Examples
+
+```
+use fluent_builder::{BoxedFluentBuilder, Eager, Override, Stack, StackMode};
+
+fn push_suffix<TStack: StackMode>(
+    builder: BoxedFluentBuilder<String, TStack>,
+) -> BoxedFluentBuilder<String, TStack> {
+    builder.fluent_mut(|s| s.push_str(" and more")).boxed()
+}
+
+let stacked = push_suffix(push_suffix(BoxedFluentBuilder::<String, Stack>::default()));
+let overridden = push_suffix(push_suffix(BoxedFluentBuilder::<String, Override>::default()));
+let eager = push_suffix(push_suffix(BoxedFluentBuilder::<String, Eager>::default()));
+
+assert_eq!("A value and more and more", stacked.into_value(|| "A value".to_owned()));
+assert_eq!("A value and more", overridden.into_value(|| "A value".to_owned()));
+assert_eq!("A value and more and more", eager.into_value(|| "A value".to_owned()));
+```
+*/
+pub trait StackMode: 'static {
+    /**
+    Whether the type of the builder's previous fluent method is kept when a new one is added to it.
+
+    This is `Stack` if the previous fluent method can be kept underneath the new one.
+    It's `Override` if the previous fluent method is always replaced,
+    so the builder's type doesn't grow with every fluent method that's added to it.
+    In that case, any previous fluent method that `stack` keeps is discarded.
+    */
+    type Keep: KeepMethod;
+
+    /**
+    Decide what to keep from the builder when a fluent method is added to it.
+
+    The builder's current value and fluent method are given, and the ones that are returned are retained.
+    The new fluent method is applied after any retained method.
+    If no value is retained then the builder will wait on a default value.
+
+    The new fluent method can also be applied to a value straight away using `NextMethod.apply`,
+    in which case it isn't stored on the builder.
+    */
+    fn stack<TValue, TMethod>(
+        value: Option<TValue>,
+        previous_fluent_method: Option<TMethod>,
+        fluent_method: NextMethod<TValue>,
+    ) -> (Option<TValue>, Option<TMethod>);
+}
+
+/**
+A fluent method that's being added to a builder.
+
+This is given to `StackMode.stack` so a stack mode can apply the method straight away.
+*/
+pub struct NextMethod<'a, TValue> {
+    apply: &'a mut dyn FnMut(TValue) -> Result<TValue, TValue>,
+}
+
+impl<'a, TValue> NextMethod<'a, TValue> {
+    fn new(apply: &'a mut dyn FnMut(TValue) -> Result<TValue, TValue>) -> Self {
+        NextMethod { apply }
+    }
+
+    /**
+    Apply the fluent method to a value straight away, instead of storing it on the builder.
+
+    If the method can't be applied yet then the value is returned unchanged in `Err`, and the method is stored as normal.
+    That's the case for context-aware methods, because there's no context to give them until the value is built.
+
+    The method is applied to the value before any previous fluent method,
+    so this should only be used when there's no previous method to keep.
+    Any previous method that's kept anyway is discarded.
+    */
+    pub fn apply(self, value: TValue) -> Result<TValue, TValue> {
+        (self.apply)(value)
+    }
+}
+
+/**
+Decides the type of the previous fluent method that's kept when a new one is added to a builder.
+
+This is implemented by `Stack`, which keeps the previous method's type, and `Override`, which replaces it with `Inline`.
+*/
+pub trait KeepMethod {
+    type Method<TValue, TContext: ?Sized, TMethod>: Method<TValue, TContext>
+    where
+        TMethod: Method<TValue, TContext>;
+
+    fn keep<TValue, TContext: ?Sized, TMethod>(
+        previous_fluent_method: Option<TMethod>,
+    ) -> Option<Self::Method<TValue, TContext, TMethod>>
+    where
+        TMethod: Method<TValue, TContext>;
+}
+
+/**
+The type of the previous fluent method that's kept by a stack mode.
+*/
+pub type Kept<TStack, TValue, TContext, TMethod> =
+    <<TStack as StackMode>::Keep as KeepMethod>::Method<TValue, TContext, TMethod>;
+
+impl KeepMethod for Stack {
+    type Method<TValue, TContext: ?Sized, TMethod>
+        = TMethod
+    where
+        TMethod: Method<TValue, TContext>;
+
+    fn keep<TValue, TContext: ?Sized, TMethod>(
+        previous_fluent_method: Option<TMethod>,
+    ) -> Option<TMethod>
+    where
+        TMethod: Method<TValue, TContext>,
+    {
+        previous_fluent_method
+    }
+}
+
+impl KeepMethod for Override {
+    type Method<TValue, TContext: ?Sized, TMethod>
+        = Inline
+    where
+        TMethod: Method<TValue, TContext>;
+
+    fn keep<TValue, TContext: ?Sized, TMethod>(_: Option<TMethod>) -> Option<Inline>
+    where
+        TMethod: Method<TValue, TContext>,
+    {
+        None
+    }
+}
+
+impl StackMode for Stack {
+    type Keep = Stack;
+
+    fn stack<TValue, TMethod>(
+        value: Option<TValue>,
+        previous_fluent_method: Option<TMethod>,
+        _: NextMethod<TValue>,
+    ) -> (Option<TValue>, Option<TMethod>) {
+        (value, previous_fluent_method)
+    }
+}
+
+impl StackMode for Override {
+    type Keep = Override;
+
+    fn stack<TValue, TMethod>(
+        _: Option<TValue>,
+        _: Option<TMethod>,
+        _: NextMethod<TValue>,
+    ) -> (Option<TValue>, Option<TMethod>) {
+        (None, None)
+    }
+}
+
+impl StackMode for Eager {
+    type Keep = Stack;
+
+    fn stack<TValue, TMethod>(
+        value: Option<TValue>,
+        previous_fluent_method: Option<TMethod>,
+        fluent_method: NextMethod<TValue>,
+    ) -> (Option<TValue>, Option<TMethod>) {
+        match (value, previous_fluent_method) {
+            (Some(value), None) => match fluent_method.apply(value) {
+                Ok(value) | Err(value) => (Some(value), None),
+            },
+            (value, previous_fluent_method) => (value, previous_fluent_method),
+        }
+    }
+}
+
 /**
 Fluent methods will be stored inline.
 */
@@ -69,34 +294,51 @@ pub type SharedStatefulFluentBuilder<TSeed, TValue, TStack = DefaultStack> =
 A structure that can contain a value, or stack mutating methods over one supplied later.
 
 The `FluentBuilder<T>` is effectively a `StatefulFluentBuilder<T, ()>`.
+Builders with context-aware fluent methods, added using `fluent_ctx`, also carry the type of their context.
 */
-pub struct FluentBuilder<TValue, TStack = DefaultStack, TStorage = DefaultStorage>
-where
-    TStorage: Storage<TValue>,
+#[must_use = "builders do nothing unless they're converted into a value"]
+pub struct FluentBuilder<
+    TValue,
+    TStack = DefaultStack,
+    TStorage = DefaultStorage,
+    TContext: ?Sized = (),
+> where
+    TStorage: Storage<TValue, TContext>,
 {
-    inner: StatefulFluentBuilder<(), TValue, TStack, TStorage>,
+    inner: StatefulFluentBuilder<(), TValue, TStack, TStorage, TContext>,
 }
 
 /**
 A stateful structure that can contain a value, or stack mutating methods over one supplied later.
 */
-pub struct StatefulFluentBuilder<TSeed, TValue, TStack = DefaultStack, TStorage = DefaultStorage>
-where
-    TStorage: Storage<TValue>,
+#[must_use = "builders do nothing unless they're converted into a value"]
+pub struct StatefulFluentBuilder<
+    TSeed,
+    TValue,
+    TStack = DefaultStack,
+    TStorage = DefaultStorage,
+    TContext: ?Sized = (),
+> where
+    TStorage: Storage<TValue, TContext>,
 {
-    inner: StatefulFluentBuilderInner<TSeed, TValue, TStorage>,
-    _marker: PhantomData<TStack>,
+    inner: DropCheck<TSeed, TValue, TStorage, TContext>,
+    #[allow(clippy::type_complexity)]
+    _marker: PhantomData<(TStack, fn(&TContext))>,
 }
 
 /**
-A boxed fluent method.
+A sequence of boxed fluent methods.
+
+The methods are kept flat, so adding one in-place doesn't nest the ones before it any deeper.
 */
-pub struct BoxedMethod<TValue>(Box<Method<TValue>>);
+pub struct BoxedMethod<TValue, TContext: ?Sized = ()>(Vec<Box<dyn Method<TValue, TContext>>>);
 
 /**
-A shared fluent method.
+A sequence of shared fluent methods.
 */
-pub struct SharedMethod<TValue>(Box<Method<TValue> + Send>);
+pub struct SharedMethod<TValue, TContext: ?Sized = ()>(
+    Vec<Box<dyn Method<TValue, TContext> + Send>>,
+);
 
 /**
 The result of attempting to pull a value out of a builder.
@@ -110,22 +352,25 @@ pub enum TryIntoValue<TValue, TBuilder> {
     Builder(TBuilder),
 }
 
-struct StatefulFluentBuilderInner<TSeed, TValue, TStorage>
+struct StatefulFluentBuilderInner<TSeed, TValue, TStorage, TContext: ?Sized = ()>
 where
-    TStorage: Storage<TValue>,
+    TStorage: Storage<TValue, TContext>,
 {
     state: State<TSeed, TValue>,
     fluent_method: Option<TStorage::Method>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 enum State<TSeed, TValue> {
     Value(TValue),
     Seed(TSeed),
 }
 
-impl<TValue, TStack, TStorage> Default for FluentBuilder<TValue, TStack, TStorage>
+impl<TValue, TStack, TStorage, TContext: ?Sized> Default
+    for FluentBuilder<TValue, TStack, TStorage, TContext>
 where
-    TStorage: Storage<TValue>,
+    TStorage: Storage<TValue, TContext>,
 {
     fn default() -> Self {
         FluentBuilder {
@@ -134,217 +379,855 @@ where
     }
 }
 
-impl<TValue, TStack, TStorage> FluentBuilder<TValue, TStack, TStorage>
+impl<TSeed, TValue, TStack, TStorage, TContext: ?Sized> Default
+    for StatefulFluentBuilder<TSeed, TValue, TStack, TStorage, TContext>
 where
-    TStorage: Storage<TValue>,
+    TSeed: Default,
+    TStorage: Storage<TValue, TContext>,
 {
-    /**
-    Create a default `FluentBuilder`.
-    */
-    pub fn new() -> Self {
-        FluentBuilder::default()
+    fn default() -> Self {
+        StatefulFluentBuilder::from_seed(TSeed::default())
     }
 }
 
-impl<TValue, TStack, TStorage> FluentBuilder<TValue, TStack, TStorage>
+impl<TValue, TStack, TStorage, TContext: ?Sized> fmt::Debug
+    for FluentBuilder<TValue, TStack, TStorage, TContext>
 where
-    TStorage: Storage<TValue>,
+    TValue: fmt::Debug,
+    TStorage: Storage<TValue, TContext>,
+    TStorage::Method: fmt::Debug,
 {
-    /**
-    Set a value on the builder.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FluentBuilder")
+            .field("state", &self.inner.as_inner().state)
+            .field("fluent_method", &self.inner.as_inner().fluent_method)
+            .finish()
+    }
+}
 
-    This will override any contained state.
-    That means if the builder currently contains fluent methods then those methods will be discarded.
-    */
-    pub fn value(self, value: TValue) -> Self {
+impl<TSeed, TValue, TStack, TStorage, TContext: ?Sized> fmt::Debug
+    for StatefulFluentBuilder<TSeed, TValue, TStack, TStorage, TContext>
+where
+    TSeed: fmt::Debug,
+    TValue: fmt::Debug,
+    TStorage: Storage<TValue, TContext>,
+    TStorage::Method: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StatefulFluentBuilder")
+            .field("state", &self.as_inner().state)
+            .field("fluent_method", &self.as_inner().fluent_method)
+            .finish()
+    }
+}
+
+impl<TValue, TStack, TStorage, TContext: ?Sized> Clone
+    for FluentBuilder<TValue, TStack, TStorage, TContext>
+where
+    TValue: Clone,
+    TStorage: Storage<TValue, TContext>,
+    TStorage::Method: Clone,
+{
+    fn clone(&self) -> Self {
         FluentBuilder {
-            inner: self.inner.value(value),
+            inner: self.inner.clone(),
         }
     }
 }
 
-impl<TValue, TStorage> FluentBuilder<TValue, Stack, TStorage>
+impl<TSeed, TValue, TStack, TStorage, TContext: ?Sized> Clone
+    for StatefulFluentBuilder<TSeed, TValue, TStack, TStorage, TContext>
 where
-    TStorage: Storage<TValue>,
+    TSeed: Clone,
+    TValue: Clone,
+    TStorage: Storage<TValue, TContext>,
+    TStorage::Method: Clone,
 {
-    /**
-    Stack a fluent method on the builder.
+    fn clone(&self) -> Self {
+        StatefulFluentBuilder::new(StatefulFluentBuilderInner {
+            state: self.as_inner().state.clone(),
+            fluent_method: self.as_inner().fluent_method.clone(),
+        })
+    }
+}
 
-    This will have the following behaviour depending on the current state of the builder if there is:
+impl<TValue, TStack, TStorage, TContext: ?Sized> PartialEq
+    for FluentBuilder<TValue, TStack, TStorage, TContext>
+where
+    TValue: PartialEq,
+    TStorage: Storage<TValue, TContext>,
+    TStorage::Method: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
 
-    - no previous value, add the fluent method. This will be applied to a later-supplied default value.
-    - a previous value, add the fluent method and retain that previous value.
-    - a previous fluent method, stack this method on top and retain any previous value.
-    */
-    pub fn fluent<TNextMethod>(
-        self,
-        fluent_method: TNextMethod,
-    ) -> FluentBuilder<TValue, Stack, Apply<TValue, TStorage::Method, ByValue<TNextMethod>>>
-    where
-        TNextMethod: FnOnce(TValue) -> TValue,
-    {
+impl<TSeed, TValue, TStack, TStorage, TContext: ?Sized> PartialEq
+    for StatefulFluentBuilder<TSeed, TValue, TStack, TStorage, TContext>
+where
+    TSeed: PartialEq,
+    TValue: PartialEq,
+    TStorage: Storage<TValue, TContext>,
+    TStorage::Method: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.as_inner().state == other.as_inner().state
+            && self.as_inner().fluent_method == other.as_inner().fluent_method
+    }
+}
+
+impl<TValue, TStack, TStorage, TContext: ?Sized> From<TValue>
+    for FluentBuilder<TValue, TStack, TStorage, TContext>
+where
+    TStorage: Storage<TValue, TContext>,
+{
+    fn from(value: TValue) -> Self {
         FluentBuilder {
-            inner: self.inner.stack(|previous_fluent_method| {
-                Apply::new(previous_fluent_method, ByValue(fluent_method))
-            }),
+            inner: StatefulFluentBuilder::from_value(value),
         }
     }
+}
 
-    /**
-    Stack a fluent method on the builder.
-
-    This method behaves the same as `fluent`, but mutates the value instead of replacing it.
-    */
-    pub fn fluent_mut<TNextMethod>(
-        self,
-        fluent_method: TNextMethod,
-    ) -> FluentBuilder<TValue, Stack, Apply<TValue, TStorage::Method, ByRefMut<TNextMethod>>>
-    where
-        TNextMethod: FnOnce(&mut TValue),
-    {
+impl<TValue, TStack, TStorage, TContext: ?Sized> From<Option<TValue>>
+    for FluentBuilder<TValue, TStack, TStorage, TContext>
+where
+    TStorage: Storage<TValue, TContext>,
+{
+    fn from(value: Option<TValue>) -> Self {
         FluentBuilder {
-            inner: self.inner.stack(|previous_fluent_method| {
-                Apply::new(previous_fluent_method, ByRefMut(fluent_method))
-            }),
+            inner: StatefulFluentBuilder::from(value),
         }
     }
 }
 
-impl<TValue, TStorage> FluentBuilder<TValue, Override, TStorage>
+impl<TSeed, TValue, TStack, TStorage, TContext: ?Sized> From<TValue>
+    for StatefulFluentBuilder<TSeed, TValue, TStack, TStorage, TContext>
 where
-    TStorage: Storage<TValue>,
+    TStorage: Storage<TValue, TContext>,
+{
+    fn from(value: TValue) -> Self {
+        StatefulFluentBuilder::from_value(value)
+    }
+}
+
+impl<TSeed, TValue, TStack, TStorage, TContext: ?Sized> From<Option<TValue>>
+    for StatefulFluentBuilder<TSeed, TValue, TStack, TStorage, TContext>
+where
+    TSeed: Default,
+    TStorage: Storage<TValue, TContext>,
 {
+    fn from(value: Option<TValue>) -> Self {
+        match value {
+            Some(value) => StatefulFluentBuilder::from_value(value),
+            None => StatefulFluentBuilder::default(),
+        }
+    }
+}
+
+impl<TValue, TBuilder> TryIntoValue<TValue, TBuilder> {
     /**
-    Create a new `StatefulFluentBuilder` from the given value.
+    Convert into a `Result`, where a value is `Ok` and a builder is `Err`.
     */
-    pub fn fluent<TNextMethod>(
-        self,
-        fluent_method: TNextMethod,
-    ) -> FluentBuilder<TValue, Override, Apply<TValue, DefaultStorage, ByValue<TNextMethod>>>
-    where
-        TNextMethod: FnOnce(TValue) -> TValue + 'static,
-    {
-        FluentBuilder {
-            inner: self.inner.fluent((), fluent_method),
+    pub fn into_result(self) -> Result<TValue, TBuilder> {
+        match self {
+            TryIntoValue::Value(value) => Ok(value),
+            TryIntoValue::Builder(builder) => Err(builder),
         }
     }
 
     /**
-    Set the fluent method on the builder.
+    Get the value, if there is one.
+    */
+    pub fn value(self) -> Option<TValue> {
+        self.into_result().ok()
+    }
 
-    This method behaves the same as `fluent`, but mutates the value instead of replacing it.
+    /**
+    Get the builder, if there is one.
     */
-    pub fn fluent_mut<TNextMethod>(
-        self,
-        fluent_method: TNextMethod,
-    ) -> FluentBuilder<TValue, Override, Apply<TValue, DefaultStorage, ByRefMut<TNextMethod>>>
+    pub fn builder(self) -> Option<TBuilder> {
+        self.into_result().err()
+    }
+
+    /**
+    Map the value, leaving a builder untouched.
+    */
+    pub fn map_value<TNextValue, TMap>(self, map: TMap) -> TryIntoValue<TNextValue, TBuilder>
     where
-        TNextMethod: FnOnce(&mut TValue) + 'static,
+        TMap: FnOnce(TValue) -> TNextValue,
     {
-        FluentBuilder {
-            inner: self.inner.fluent_mut((), fluent_method),
+        match self {
+            TryIntoValue::Value(value) => TryIntoValue::Value(map(value)),
+            TryIntoValue::Builder(builder) => TryIntoValue::Builder(builder),
         }
     }
-}
 
-impl<TValue, TStack, TStorage> FluentBuilder<TValue, TStack, TStorage>
-where
-    TStorage: Storage<TValue>,
-{
     /**
-    Convert the fluent builder into a value.
-
-    This method will consume the builder and return a constructed `T`.
-    This will have the following behaviour if the builder contains:
-
-    - no value or fluent methods, then the default value is constructed.
-    - a value, then that value is returned.
-    - no value but fluent methods, then the methods are applied over the default value.
-    - a value and fluent methods, then the methods are applied over that value.
+    Map the builder, leaving a value untouched.
     */
-    pub fn into_value<TDefault>(self, default_value: TDefault) -> TValue
+    pub fn map_builder<TNextBuilder, TMap>(self, map: TMap) -> TryIntoValue<TValue, TNextBuilder>
     where
-        TDefault: FnOnce() -> TValue + 'static,
+        TMap: FnOnce(TBuilder) -> TNextBuilder,
     {
-        self.inner.into_value(move |_| default_value())
+        match self {
+            TryIntoValue::Value(value) => TryIntoValue::Value(value),
+            TryIntoValue::Builder(builder) => TryIntoValue::Builder(map(builder)),
+        }
     }
 
     /**
-    Attempt to take a value from the builder.
-
-    If the builder doesn't contain a concrete value then it is returned in the `Builder` variant.
+    Get the value, or compute one from the builder.
 
     # Examples
 
     ```
-    # use fluent_builder::{Stack, FluentBuilder, TryIntoValue};
-    let builder = FluentBuilder::<String, Stack>::new()
-        .value("A value".to_owned())
-        .fluent_mut(|mut s| s.push_str(" and more"));
+    # use fluent_builder::{Stack, FluentBuilder};
+    let value = FluentBuilder::<String, Stack>::new()
+        .fluent_mut(|s| s.push_str(" and more"))
+        .try_into_value()
+        .unwrap_or_else(|builder| builder.into_value(|| "A default value".to_owned()));
 
-    match builder.try_into_value() {
-        TryIntoValue::Value(value) => {
-            // The builder has a value that we can use
-            assert_eq!("A value and more", value);
-        },
-        TryIntoValue::Builder(builder) => {
-            // The builder doesn't have a value but we can still use it
-            let value = builder.into_value(|| String::new());
-    #       panic!("expected value");
-        }
-    }
+    assert_eq!("A default value and more", value);
     ```
     */
-    pub fn try_into_value(self) -> TryIntoValue<TValue, Self> {
-        match self.inner.try_into_value() {
-            TryIntoValue::Builder(inner) => TryIntoValue::Builder(FluentBuilder { inner }),
-            TryIntoValue::Value(value) => TryIntoValue::Value(value),
+    pub fn unwrap_or_else<TDefault>(self, default_value: TDefault) -> TValue
+    where
+        TDefault: FnOnce(TBuilder) -> TValue,
+    {
+        match self {
+            TryIntoValue::Value(value) => value,
+            TryIntoValue::Builder(builder) => default_value(builder),
         }
     }
 }
 
-impl<TValue, TStack, TStorage> FluentBuilder<TValue, TStack, TStorage>
+impl<TValue, TStack, TStorage, TContext: ?Sized> FluentBuilder<TValue, TStack, TStorage, TContext>
 where
-    TValue: 'static,
-    TStorage: Storage<TValue> + 'static,
+    TStorage: Storage<TValue, TContext>,
 {
     /**
-    Box a fluent builder so it can be easily captured as a field without generics.
+    Create a default `FluentBuilder`.
     */
-    pub fn boxed(self) -> BoxedFluentBuilder<TValue, TStack> {
-        FluentBuilder {
-            inner: self.inner.boxed(),
-        }
+    pub fn new() -> Self {
+        FluentBuilder::default()
     }
 }
 
-impl<TValue, TStack, TStorage> FluentBuilder<TValue, TStack, TStorage>
-where
-    TValue: 'static,
-    TStorage: Storage<TValue>,
-    TStorage::Method: Send + 'static,
-{
+impl<TValue, TStack> FluentBuilder<TValue, TStack, DefaultStorage> {
     /**
-    Box a fluent builder so it can be easily shared.
+    Create a new `FluentBuilder` from the given function.
+
+    The function will be applied to a later-supplied default value.
+    This is the inverse of `into_fn`.
     */
-    pub fn shared(self) -> SharedFluentBuilder<TValue, TStack> {
+    #[track_caller]
+    pub fn from_fn<TNextMethod>(
+        fluent_method: TNextMethod,
+    ) -> FluentBuilder<TValue, TStack, Apply<TValue, DefaultStorage, ByValue<TNextMethod>>>
+    where
+        TNextMethod: FnOnce(TValue) -> TValue,
+    {
         FluentBuilder {
-            inner: self.inner.shared(),
+            inner: StatefulFluentBuilder::<(), TValue, TStack, Inline>::from_fluent(
+                (),
+                fluent_method,
+            ),
         }
     }
 }
 
-impl<TSeed, TValue, TStack, TStorage> StatefulFluentBuilder<TSeed, TValue, TStack, TStorage>
+impl<TValue, TStack> FluentBuilder<TValue, TStack, Shared> {
+    /**
+    Create a new `FluentBuilder` from the given function.
+
+    The function will be applied to a later-supplied default value.
+    This is the inverse of `into_fn`.
+    */
+    #[track_caller]
+    pub fn from_fn<TNextMethod>(fluent_method: TNextMethod) -> FluentBuilder<TValue, TStack, Shared>
+    where
+        TValue: Send + 'static,
+        TNextMethod: FnOnce(TValue) -> TValue + Send + 'static,
+    {
+        FluentBuilder {
+            inner: StatefulFluentBuilder::<(), TValue, TStack, Shared>::from_fluent(
+                (),
+                fluent_method,
+            ),
+        }
+    }
+}
+
+impl<TValue, TStack> FluentBuilder<TValue, TStack, Boxed> {
+    /**
+    Create a new `FluentBuilder` from the given function.
+
+    The function will be applied to a later-supplied default value.
+    This is the inverse of `into_fn`.
+    */
+    #[track_caller]
+    pub fn from_fn<TNextMethod>(fluent_method: TNextMethod) -> FluentBuilder<TValue, TStack, Boxed>
+    where
+        TValue: 'static,
+        TNextMethod: FnOnce(TValue) -> TValue + 'static,
+    {
+        FluentBuilder {
+            inner: StatefulFluentBuilder::<(), TValue, TStack, Boxed>::from_fluent(
+                (),
+                fluent_method,
+            ),
+        }
+    }
+}
+
+impl<TValue, TStack, TStorage, TContext: ?Sized> FluentBuilder<TValue, TStack, TStorage, TContext>
+where
+    TStorage: Storage<TValue, TContext>,
+{
+    /**
+    Set a value on the builder.
+
+    This will override any contained state.
+    That means if the builder currently contains fluent methods then those methods will be discarded.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    pub fn value(self, value: TValue) -> Self {
+        FluentBuilder {
+            inner: self.inner.value(value),
+        }
+    }
+
+    /**
+    Label the most recently added fluent method.
+
+    The label is reported instead of the method's caller location when it's applied,
+    such as in debug events when the `log` feature is enabled.
+    Storages that don't keep closures, like `Ops`, ignore labels.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    pub fn label(self, label: &'static str) -> Self {
+        FluentBuilder {
+            inner: self.inner.label(label),
+        }
+    }
+
+    /**
+    Drop the builder without converting it into a value.
+
+    This is the same as letting the builder go out of scope,
+    but won't be flagged when the `debug-drop-check` feature is enabled.
+    */
+    pub fn discard(self) {
+        self.inner.discard()
+    }
+}
+
+impl<TValue, TStack, TStorage> FluentBuilder<TValue, TStack, TStorage>
 where
     TStorage: Storage<TValue>,
 {
-    fn new(inner: StatefulFluentBuilderInner<TSeed, TValue, TStorage>) -> Self {
+    /**
+    Apply any pending fluent methods to the value contained in the builder.
+
+    If the builder doesn't contain a value then it's returned unchanged,
+    because its fluent methods are still waiting on a default.
+    Otherwise the builder will only contain the resulting value, which is the same as what `into_value` would return.
+
+    # Examples
+
+    ```
+    # use fluent_builder::{FluentBuilder, Stack};
+    let builder = FluentBuilder::<String, Stack>::default()
+        .value("A value".to_owned())
+        .fluent_mut(|s| s.push_str(" and more"))
+        .boxed()
+        .compact();
+
+    assert_eq!("A value and more", builder.into_value(String::new));
+    ```
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    pub fn compact(self) -> Self {
+        FluentBuilder {
+            inner: self.inner.compact(),
+        }
+    }
+}
+
+impl<TValue, TStack, TStorage, TContext: ?Sized> FluentBuilder<TValue, TStack, TStorage, TContext>
+where
+    TStack: StackMode,
+    TStorage: Storage<TValue, TContext>,
+{
+    #[allow(clippy::type_complexity)]
+    fn stack<TNextContext: ?Sized, TNextMethod>(
+        self,
+        fluent_method: TNextMethod,
+        location: &'static Location<'static>,
+    ) -> FluentBuilder<
+        TValue,
+        TStack,
+        Apply<TValue, Kept<TStack, TValue, TNextContext, TStorage::Method>, TNextMethod>,
+        TNextContext,
+    >
+    where
+        TNextMethod: Immediate<TValue>,
+        TStorage::Method: Method<TValue, TNextContext>,
+        Apply<TValue, Kept<TStack, TValue, TNextContext, TStorage::Method>, TNextMethod>:
+            Method<TValue, TNextContext>,
+    {
+        let StatefulFluentBuilderInner {
+            state,
+            fluent_method: previous_fluent_method,
+        } = self.inner.into_inner();
+
+        let value = match state {
+            State::Value(value) => Some(value),
+            State::Seed(()) => None,
+        };
+
+        let (value, previous_fluent_method, fluent_method) =
+            stack_method::<TStack, _, _, _>(value, previous_fluent_method, fluent_method, location);
+
+        FluentBuilder {
+            inner: StatefulFluentBuilder::new(StatefulFluentBuilderInner {
+                state: value.map_or(State::Seed(()), State::Value),
+                fluent_method: fluent_method.map(|next| {
+                    let previous_fluent_method =
+                        TStack::Keep::keep::<TValue, TNextContext, _>(previous_fluent_method);
+
+                    Apply::new(previous_fluent_method, next, location)
+                }),
+            }),
+        }
+    }
+
+    /**
+    Add a fluent method to the builder.
+
+    What happens to the builder's current value and fluent methods depends on its stack mode.
+    For a `Stack` builder if there is:
+
+    - no previous value, add the fluent method. This will be applied to a later-supplied default value.
+    - a previous value, add the fluent method and retain that previous value.
+    - a previous fluent method, stack this method on top and retain any previous value.
+
+    For an `Override` builder if there is:
+
+    - no previous value, add the fluent method. This will be applied to a later-supplied default value.
+    - a previous value, add the fluent method and remove that previous value.
+    - a previous fluent method, that method will be replaced with the given one.
+
+    An `Eager` builder behaves the same as a `Stack` builder,
+    except if there's a previous value and no previous fluent method then the method is applied to that value immediately.
+    If the method panics while being applied immediately then the builder is dropped.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn fluent<TNextMethod>(
+        self,
+        fluent_method: TNextMethod,
+    ) -> FluentBuilder<
+        TValue,
+        TStack,
+        Apply<TValue, Kept<TStack, TValue, TContext, TStorage::Method>, ByValue<TNextMethod>>,
+        TContext,
+    >
+    where
+        TNextMethod: FnOnce(TValue) -> TValue,
+    {
+        self.stack(ByValue(fluent_method), Location::caller())
+    }
+
+    /**
+    Add a fluent method to the builder.
+
+    This method behaves the same as `fluent`, but mutates the value instead of replacing it.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn fluent_mut<TNextMethod>(
+        self,
+        fluent_method: TNextMethod,
+    ) -> FluentBuilder<
+        TValue,
+        TStack,
+        Apply<TValue, Kept<TStack, TValue, TContext, TStorage::Method>, ByRefMut<TNextMethod>>,
+        TContext,
+    >
+    where
+        TNextMethod: FnOnce(&mut TValue),
+    {
+        self.stack(ByRefMut(fluent_method), Location::caller())
+    }
+
+    /**
+    Add a fluent method to the builder.
+
+    This method behaves the same as `fluent_mut`, but accepts methods in the style of by-reference builders,
+    that take `&mut self` and return `&mut Self`.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn fluent_ref<TNextMethod>(
+        self,
+        fluent_method: TNextMethod,
+    ) -> FluentBuilder<
+        TValue,
+        TStack,
+        Apply<
+            TValue,
+            Kept<TStack, TValue, TContext, TStorage::Method>,
+            ByRefMut<impl FnOnce(&mut TValue)>,
+        >,
+        TContext,
+    >
+    where
+        TNextMethod: FnOnce(&mut TValue) -> &mut TValue,
+    {
+        self.fluent_mut(move |value| {
+            fluent_method(value);
+        })
+    }
+
+    /**
+    Add a fluent method to the builder that wraps the methods added after it.
+
+    The method is given the value along with a `next` function that applies the remaining fluent methods to it,
+    so it can run code both before and after them.
+    If the method doesn't call `next` then the remaining methods won't be applied.
+    Calling `next` more than once will panic.
+
+    For an `Override` builder, adding a later method will replace this one, so there's nothing for it to wrap.
+
+    # Examples
+
+    ```
+    # use fluent_builder::{FluentBuilder, Stack};
+    let builder = FluentBuilder::<Vec<i32>, Stack>::default()
+        .fluent_around(|v, next| {
+            // Keep the value sorted, regardless of what later methods do
+            let mut v = next(v);
+            v.sort();
+            v
+        })
+        .fluent_mut(|v| v.push(3))
+        .fluent_mut(|v| v.push(1));
+
+    assert_eq!(vec![1, 2, 3], builder.into_value(|| vec![2]));
+    ```
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn fluent_around<TNextMethod>(
+        self,
+        fluent_method: TNextMethod,
+    ) -> FluentBuilder<
+        TValue,
+        TStack,
+        Apply<TValue, Kept<TStack, TValue, TContext, TStorage::Method>, Around<TNextMethod>>,
+        TContext,
+    >
+    where
+        TNextMethod: FnOnce(TValue, &mut dyn FnMut(TValue) -> TValue) -> TValue,
+    {
+        self.stack(Around(fluent_method), Location::caller())
+    }
+}
+
+impl<TValue, TStack, TStorage> FluentBuilder<TValue, TStack, TStorage>
+where
+    TStorage: Storage<TValue>,
+{
+    /**
+    Convert the fluent builder into a value.
+
+    This method will consume the builder and return a constructed `T`.
+    This will have the following behaviour if the builder contains:
+
+    - no value or fluent methods, then the default value is constructed.
+    - a value, then that value is returned.
+    - no value but fluent methods, then the methods are applied over the default value.
+    - a value and fluent methods, then the methods are applied over that value.
+    */
+    pub fn into_value<TDefault>(self, default_value: TDefault) -> TValue
+    where
+        TDefault: FnOnce() -> TValue,
+    {
+        self.inner.into_value(move |_| default_value())
+    }
+
+    /**
+    Convert the fluent builder into a function that can be applied to a value later.
+
+    The returned function has the same behaviour as `into_value` if the builder contains:
+
+    - a value, then the given value is ignored and the fluent methods are applied over the contained one.
+    - no value, then the fluent methods are applied over the given value.
+
+    # Examples
+
+    ```
+    # use fluent_builder::{Stack, FluentBuilder};
+    let f = FluentBuilder::<String, Stack>::new()
+        .fluent_mut(|s| s.push_str(" and more"))
+        .into_fn();
+
+    assert_eq!("A value and more", f("A value".to_owned()));
+    ```
+    */
+    pub fn into_fn(self) -> impl FnOnce(TValue) -> TValue {
+        move |value| self.into_value(move || value)
+    }
+
+    /**
+    Attempt to take a value from the builder.
+
+    If the builder doesn't contain a concrete value then it is returned in the `Builder` variant.
+
+    # Examples
+
+    ```
+    # use fluent_builder::{Stack, FluentBuilder, TryIntoValue};
+    let builder = FluentBuilder::<String, Stack>::new()
+        .value("A value".to_owned())
+        .fluent_mut(|mut s| s.push_str(" and more"));
+
+    match builder.try_into_value() {
+        TryIntoValue::Value(value) => {
+            // The builder has a value that we can use
+            assert_eq!("A value and more", value);
+        },
+        TryIntoValue::Builder(builder) => {
+            // The builder doesn't have a value but we can still use it
+            let value = builder.into_value(|| String::new());
+    #       panic!("expected value");
+        }
+    }
+    ```
+    */
+    pub fn try_into_value(self) -> TryIntoValue<TValue, Self> {
+        match self.inner.try_into_value() {
+            TryIntoValue::Builder(inner) => TryIntoValue::Builder(FluentBuilder { inner }),
+            TryIntoValue::Value(value) => TryIntoValue::Value(value),
+        }
+    }
+
+    /**
+    Apply the fluent builder to an existing value.
+
+    This method will consume the builder and update the given `T` in-place.
+    This will have the following behaviour if the builder contains:
+
+    - no value or fluent methods, then the target is left unchanged.
+    - a value, then the target is overwritten with that value.
+    - no value but fluent methods, then the methods are applied over the target.
+    - a value and fluent methods, then the target is overwritten and the methods are applied over it.
+
+    The target is moved out while fluent methods are applied over it, so if one panics then the process is aborted.
+    Use `apply_to_or_else` to leave a fallback in the target instead.
+
+    # Examples
+
+    ```
+    # use fluent_builder::{Stack, FluentBuilder};
+    let mut value = "A value".to_owned();
+
+    FluentBuilder::<String, Stack>::new()
+        .fluent_mut(|s| s.push_str(" and more"))
+        .apply_to(&mut value);
+
+    assert_eq!("A value and more", value);
+    ```
+    */
+    pub fn apply_to(self, target: &mut TValue) {
+        self.inner.apply_to(target)
+    }
+
+    /**
+    Apply the fluent builder to an existing value, using a function to construct a fallback for the target.
+
+    This method behaves the same as `apply_to`, but if a fluent method panics then the target is left with the fallback instead of aborting.
+    The fallback is only constructed if fluent methods are applied over the target.
+    */
+    pub fn apply_to_or_else<TDefault>(self, target: &mut TValue, default_value: TDefault)
+    where
+        TDefault: FnOnce() -> TValue,
+    {
+        self.inner.apply_to_or_else(target, default_value)
+    }
+}
+
+impl<TValue, TStack, TStorage, TContext: ?Sized> FluentBuilder<TValue, TStack, TStorage, TContext>
+where
+    TStorage: Storage<TValue, TContext>,
+{
+    /**
+    Convert the fluent builder into a value, giving the context to any context-aware fluent methods.
+
+    This method behaves the same as `into_value`.
+    Builders with methods added using `fluent_ctx` can only be converted into a value this way.
+    */
+    pub fn into_value_with<TDefault>(self, ctx: &TContext, default_value: TDefault) -> TValue
+    where
+        TDefault: FnOnce() -> TValue,
+    {
+        self.inner.into_value_with(ctx, move |_| default_value())
+    }
+
+    /**
+    Attempt to take a value from the builder, giving the context to any context-aware fluent methods.
+
+    This method behaves the same as `try_into_value`.
+    */
+    pub fn try_into_value_with(self, ctx: &TContext) -> TryIntoValue<TValue, Self> {
+        match self.inner.try_into_value_with(ctx) {
+            TryIntoValue::Builder(inner) => TryIntoValue::Builder(FluentBuilder { inner }),
+            TryIntoValue::Value(value) => TryIntoValue::Value(value),
+        }
+    }
+
+    /**
+    Apply the fluent builder to an existing value, giving the context to any context-aware fluent methods.
+
+    This method behaves the same as `apply_to`.
+    */
+    pub fn apply_to_with(self, ctx: &TContext, target: &mut TValue) {
+        self.inner.apply_to_with(ctx, target)
+    }
+
+    /**
+    Apply the fluent builder to an existing value, giving the context to any context-aware fluent methods
+    and using a function to construct a fallback for the target.
+
+    This method behaves the same as `apply_to_or_else`.
+    */
+    pub fn apply_to_with_or_else<TDefault>(
+        self,
+        ctx: &TContext,
+        target: &mut TValue,
+        default_value: TDefault,
+    ) where
+        TDefault: FnOnce() -> TValue,
+    {
+        self.inner.apply_to_with_or_else(ctx, target, default_value)
+    }
+}
+
+impl<TValue, TStack, TStorage> FluentBuilder<TValue, TStack, TStorage>
+where
+    Self: 'static,
+    TStorage: Storage<TValue>,
+{
+    /**
+    Convert the fluent builder into a boxed function that can be applied to a value later.
+
+    This method behaves the same as `into_fn`, but the returned function can be captured without generics.
+    */
+    pub fn into_boxed_fn(self) -> Box<dyn FnOnce(TValue) -> TValue> {
+        Box::new(self.into_fn())
+    }
+}
+
+impl<TValue, TStack, TStorage> FluentBuilder<TValue, TStack, TStorage>
+where
+    TValue: Default,
+    TStorage: Storage<TValue>,
+{
+    /**
+    Convert the fluent builder into a value, using `Default` to construct a missing value.
+
+    This method behaves the same as `into_value(TValue::default)`.
+
+    # Examples
+
+    ```
+    # use fluent_builder::{Stack, FluentBuilder};
+    let value = FluentBuilder::<String, Stack>::new()
+        .fluent_mut(|s| s.push_str("A value"))
+        .into_default_value();
+
+    assert_eq!("A value", value);
+    ```
+    */
+    pub fn into_default_value(self) -> TValue {
+        self.into_value(TValue::default)
+    }
+}
+
+impl<TValue, TStack, TStorage, TContext: ?Sized> FluentBuilder<TValue, TStack, TStorage, TContext>
+where
+    TValue: 'static,
+    TStorage: Storage<TValue, TContext>,
+    TStorage::Method: 'static,
+{
+    /**
+    Box a fluent builder so it can be easily captured as a field without generics.
+    */
+    pub fn boxed(self) -> FluentBuilder<TValue, TStack, Boxed, TContext> {
+        FluentBuilder {
+            inner: self.inner.boxed(),
+        }
+    }
+}
+
+impl<TValue, TStack, TStorage, TContext: ?Sized> FluentBuilder<TValue, TStack, TStorage, TContext>
+where
+    TValue: 'static,
+    TStorage: Storage<TValue, TContext>,
+    TStorage::Method: Send + 'static,
+{
+    /**
+    Box a fluent builder so it can be easily shared.
+    */
+    pub fn shared(self) -> FluentBuilder<TValue, TStack, Shared, TContext> {
+        FluentBuilder {
+            inner: self.inner.shared(),
+        }
+    }
+}
+
+impl<TSeed, TValue, TStack, TStorage, TContext: ?Sized>
+    StatefulFluentBuilder<TSeed, TValue, TStack, TStorage, TContext>
+where
+    TStorage: Storage<TValue, TContext>,
+{
+    fn new(inner: StatefulFluentBuilderInner<TSeed, TValue, TStorage, TContext>) -> Self {
         StatefulFluentBuilder {
-            inner: inner,
+            inner: DropCheck::new(inner),
             _marker: PhantomData,
         }
     }
 
+    fn as_inner(&self) -> &StatefulFluentBuilderInner<TSeed, TValue, TStorage, TContext> {
+        self.inner.get()
+    }
+
+    fn as_inner_mut(
+        &mut self,
+    ) -> &mut StatefulFluentBuilderInner<TSeed, TValue, TStorage, TContext> {
+        self.inner.get_mut()
+    }
+
+    fn into_inner(self) -> StatefulFluentBuilderInner<TSeed, TValue, TStorage, TContext> {
+        self.inner.into_inner()
+    }
+
+    /**
+    Drop the builder without converting it into a value.
+
+    This is the same as letting the builder go out of scope,
+    but won't be flagged when the `debug-drop-check` feature is enabled.
+    */
+    pub fn discard(self) {
+        drop(self.into_inner());
+    }
+
     /**
     Create a new `StatefulFluentBuilder` from the given value.
     */
@@ -371,18 +1254,111 @@ where
     This will override any contained state.
     That means if the builder currently contains fluent methods then those methods will be discarded.
     */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
     pub fn value(self, value: TValue) -> Self {
+        self.discard();
+
         StatefulFluentBuilder::new(StatefulFluentBuilderInner {
             state: State::Value(value),
             fluent_method: None,
         })
     }
+
+    /**
+    Label the most recently added fluent method.
+
+    This has the same behaviour as `FluentBuilder.label`.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    pub fn label(mut self, label: &'static str) -> Self {
+        if let Some(ref mut fluent_method) = self.as_inner_mut().fluent_method {
+            fluent_method.set_label(label);
+        }
+
+        self
+    }
+
+    /**
+    Get the seed that will be given to the default value, if the builder doesn't contain a value.
+    */
+    pub fn seed(&self) -> Option<&TSeed> {
+        match self.as_inner().state {
+            State::Seed(ref seed) => Some(seed),
+            State::Value(_) => None,
+        }
+    }
+
+    /**
+    Transform the seed that will be given to the default value.
+
+    Any fluent methods are retained.
+    If the builder contains a value instead of a seed then the function isn't called.
+
+    # Examples
+
+    ```
+    # use fluent_builder::{Stack, StatefulFluentBuilder};
+    let value = StatefulFluentBuilder::<u16, String, Stack>::from_seed(80)
+        .fluent_seed(|port| port + 8000)
+        .fluent_mut_with_seed(|port, s| s.push_str(&format!(":{}", port)))
+        .into_value(|_| "localhost".to_owned());
+
+    assert_eq!("localhost:8080", value);
+    ```
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    pub fn fluent_seed<TNextSeed>(self, fluent_seed: TNextSeed) -> Self
+    where
+        TNextSeed: FnOnce(TSeed) -> TSeed,
+    {
+        let StatefulFluentBuilderInner {
+            state,
+            fluent_method,
+        } = self.into_inner();
+
+        let state = match state {
+            State::Seed(seed) => State::Seed(fluent_seed(seed)),
+            state => state,
+        };
+
+        StatefulFluentBuilder::new(StatefulFluentBuilderInner {
+            state,
+            fluent_method,
+        })
+    }
+}
+
+impl<TSeed, TValue, TStack, TStorage> StatefulFluentBuilder<TSeed, TValue, TStack, TStorage>
+where
+    TStorage: Storage<TValue>,
+{
+    /**
+    Apply any pending fluent methods to the value contained in the builder.
+
+    This has the same behaviour as `FluentBuilder.compact`.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    pub fn compact(self) -> Self {
+        match self.into_inner() {
+            StatefulFluentBuilderInner {
+                state: State::Value(value),
+                fluent_method: Some(mut fluent_method),
+            } => StatefulFluentBuilder::from_value(apply_method(
+                &mut fluent_method,
+                &(),
+                value,
+                Base::Value,
+            )),
+            inner => StatefulFluentBuilder::new(inner),
+        }
+    }
 }
 
 impl<TSeed, TValue, TStack> StatefulFluentBuilder<TSeed, TValue, TStack, DefaultStorage> {
     /**
     Create a new `StatefulFluentBuilder` from the given seed and fluent method.
     */
+    #[track_caller]
     pub fn from_fluent<TNextStorage>(
         seed: TSeed,
         fluent_method: TNextStorage,
@@ -395,7 +1371,7 @@ impl<TSeed, TValue, TStack> StatefulFluentBuilder<TSeed, TValue, TStack, Default
     where
         TNextStorage: FnOnce(TValue) -> TValue,
     {
-        let fluent_method = Apply::new(None, ByValue(fluent_method));
+        let fluent_method = Apply::new(None, ByValue(fluent_method), Location::caller());
         StatefulFluentBuilder::new(StatefulFluentBuilderInner {
             state: State::Seed(seed),
             fluent_method: Some(fluent_method),
@@ -407,6 +1383,7 @@ impl<TSeed, TValue, TStack> StatefulFluentBuilder<TSeed, TValue, TStack, Default
 
     This method is the same as `from_fluent`, but mutates the value instead of replacing it.
     */
+    #[track_caller]
     pub fn from_fluent_mut<TNextStorage>(
         seed: TSeed,
         fluent_method: TNextStorage,
@@ -419,7 +1396,7 @@ impl<TSeed, TValue, TStack> StatefulFluentBuilder<TSeed, TValue, TStack, Default
     where
         TNextStorage: FnOnce(&mut TValue),
     {
-        let fluent_method = Apply::new(None, ByRefMut(fluent_method));
+        let fluent_method = Apply::new(None, ByRefMut(fluent_method), Location::caller());
         StatefulFluentBuilder::new(StatefulFluentBuilderInner {
             state: State::Seed(seed),
             fluent_method: Some(fluent_method),
@@ -431,6 +1408,7 @@ impl<TSeed, TValue, TStack> StatefulFluentBuilder<TSeed, TValue, TStack, Shared>
     /**
     Create a new `StatefulFluentBuilder` from the given seed and fluent method.
     */
+    #[track_caller]
     pub fn from_fluent<TNextStorage>(
         seed: TSeed,
         fluent_method: TNextStorage,
@@ -448,6 +1426,7 @@ impl<TSeed, TValue, TStack> StatefulFluentBuilder<TSeed, TValue, TStack, Shared>
 
     This method is the same as `from_fluent`, but mutates the value instead of replacing it.
     */
+    #[track_caller]
     pub fn from_fluent_mut<TNextStorage>(
         seed: TSeed,
         fluent_method: TNextStorage,
@@ -465,6 +1444,7 @@ impl<TSeed, TValue, TStack> StatefulFluentBuilder<TSeed, TValue, TStack, Boxed>
     /**
     Create a new `StatefulFluentBuilder` from the given seed and fluent method.
     */
+    #[track_caller]
     pub fn from_fluent<TNextStorage>(
         seed: TSeed,
         fluent_method: TNextStorage,
@@ -482,6 +1462,7 @@ impl<TSeed, TValue, TStack> StatefulFluentBuilder<TSeed, TValue, TStack, Boxed>
 
     This method is the same as `from_fluent`, but mutates the value instead of replacing it.
     */
+    #[track_caller]
     pub fn from_fluent_mut<TNextStorage>(
         seed: TSeed,
         fluent_method: TNextStorage,
@@ -512,22 +1493,124 @@ where
     */
     pub fn into_value<TDefault>(self, default_value: TDefault) -> TValue
     where
-        TDefault: FnOnce(TSeed) -> TValue + 'static,
+        TDefault: FnOnce(TSeed) -> TValue,
+    {
+        self.into_value_with(&(), default_value)
+    }
+
+    /**
+    Convert the fluent builder into a value, using `From` to construct a missing value from the seed.
+
+    This method behaves the same as `into_value(TValue::from)`.
+
+    # Examples
+
+    ```
+    # use fluent_builder::{Stack, StatefulFluentBuilder};
+    let value = StatefulFluentBuilder::<&str, String, Stack>::from_seed("A value")
+        .fluent_mut("and more", |more, s| {
+            s.push(' ');
+            s.push_str(more);
+        })
+        .into_value_from_seed();
+
+    assert_eq!("A value and more", value);
+    ```
+    */
+    pub fn into_value_from_seed(self) -> TValue
+    where
+        TValue: From<TSeed>,
+    {
+        self.into_value(TValue::from)
+    }
+
+    /**
+    Use the fluent builder as a template to build a value for each of the given seeds.
+
+    The default is constructed from each seed, and then the builder's fluent methods are replayed over it.
+    The fluent methods are shared by every value instead of being rebuilt or cloned for each one,
+    so they need to be replayable through a reference.
+    That includes functions and closures that implement `Fn`, as long as any seeds given to them can be cloned,
+    and operations stored by `Ops` that can be cloned.
+    Closures written directly as an argument to a fluent method are only inferred as `FnOnce`,
+    so they need to be bound to a variable first.
+    Boxed and shared methods can't be replayed, because they can only be applied once.
+
+    If the builder contains a value instead of a seed then the fluent methods are replayed over a clone of that value
+    for each of the given seeds, and the seeds aren't used to construct a default.
+
+    # Examples
+
+    ```
+    # use fluent_builder::{Stack, StatefulFluentBuilder};
+    let push_suffix = |suffix, s: &mut String| s.push_str(suffix);
+
+    let template = StatefulFluentBuilder::<&str, String, Stack>::default()
+        .fluent_mut(".example.com", push_suffix);
+
+    let hosts: Vec<_> = template
+        .build_many(vec!["host-1", "host-2"], |host| host.to_owned())
+        .collect();
+
+    assert_eq!(vec!["host-1.example.com", "host-2.example.com"], hosts);
+    ```
+
+    Operations stored by `Ops` can also be replayed:
+
+    ```
+    # use fluent_builder::{Op, Ops, Stack, StatefulFluentBuilder};
+    #[derive(Clone)]
+    struct Suffix(&'static str);
+
+    impl Op<String> for Suffix {
+        fn apply(self, mut value: String) -> String {
+            value.push_str(self.0);
+            value
+        }
+    }
+
+    let template = StatefulFluentBuilder::<u32, String, Stack, Ops<Suffix>>::default()
+        .op(Suffix(".example.com"));
+
+    let hosts: Vec<_> = template
+        .build_many(1..=3, |id| format!("host-{}", id))
+        .collect();
+
+    assert_eq!(vec!["host-1.example.com", "host-2.example.com", "host-3.example.com"], hosts);
+    ```
+    */
+    pub fn build_many<TSeeds, TDefault>(
+        self,
+        seeds: TSeeds,
+        mut default_value: TDefault,
+    ) -> impl Iterator<Item = TValue>
+    where
+        TSeeds: IntoIterator<Item = TSeed>,
+        TDefault: FnMut(TSeed) -> TValue,
+        TValue: Clone,
+        TStorage::Method: Replay<TValue>,
     {
         let StatefulFluentBuilderInner {
             state,
-            mut fluent_method,
-        } = self.inner;
+            fluent_method,
+        } = self.into_inner();
 
-        let default = match state {
-            State::Value(value) => value,
-            State::Seed(seed) => default_value(seed),
+        let value = match state {
+            State::Value(value) => Some(value),
+            State::Seed(_) => None,
         };
 
-        match fluent_method {
-            Some(ref mut fluent_method) => fluent_method.apply(default),
-            None => default,
-        }
+        seeds.into_iter().map(move |seed| {
+            let (value, base) = match value {
+                Some(ref value) => (value.clone(), Base::Value),
+                None => (default_value(seed), Base::Default),
+            };
+
+            match fluent_method {
+                Some(ref fluent_method) => replay_method(fluent_method, &(), value, base),
+                None => value,
+            }
+        })
     }
 
     /**
@@ -553,38 +1636,178 @@ where
     #       panic!("expected value");
         }
     }
-    ```
+    ```
+    */
+    pub fn try_into_value(self) -> TryIntoValue<TValue, Self> {
+        self.try_into_value_with(&())
+    }
+
+    /**
+    Apply the fluent builder to an existing value.
+
+    This method will consume the builder and update the given `T` in-place.
+    This will have the following behaviour if the builder contains:
+
+    - no value or fluent methods, then the target is left unchanged.
+    - a value, then the target is overwritten with that value.
+    - no value but fluent methods, then the methods are applied over the target.
+    - a value and fluent methods, then the target is overwritten and the methods are applied over it.
+
+    Any seed is discarded, because the target takes the place of the default value.
+    The target is moved out while fluent methods are applied over it, so if one panics then the process is aborted.
+    Use `apply_to_or_else` to leave a fallback in the target instead.
+    */
+    pub fn apply_to(self, target: &mut TValue) {
+        self.apply_to_with(&(), target)
+    }
+
+    /**
+    Apply the fluent builder to an existing value, using a function to construct a fallback for the target.
+
+    This has the same behaviour as `FluentBuilder.apply_to_or_else`.
+    */
+    pub fn apply_to_or_else<TDefault>(self, target: &mut TValue, default_value: TDefault)
+    where
+        TDefault: FnOnce() -> TValue,
+    {
+        self.apply_to_with_or_else(&(), target, default_value)
+    }
+}
+
+impl<TSeed, TValue, TStack, TStorage, TContext: ?Sized>
+    StatefulFluentBuilder<TSeed, TValue, TStack, TStorage, TContext>
+where
+    TStorage: Storage<TValue, TContext>,
+{
+    /**
+    Convert the fluent builder into a value, giving the context to any context-aware fluent methods.
+
+    This has the same behaviour as `FluentBuilder.into_value_with`.
+    */
+    pub fn into_value_with<TDefault>(self, ctx: &TContext, default_value: TDefault) -> TValue
+    where
+        TDefault: FnOnce(TSeed) -> TValue,
+    {
+        let (default, base, fluent_method) = self.into_base(default_value);
+
+        match fluent_method {
+            Some(mut fluent_method) => apply_method(&mut fluent_method, ctx, default, base),
+            None => default,
+        }
+    }
+
+    /**
+    Attempt to take a value from the builder, giving the context to any context-aware fluent methods.
+
+    This has the same behaviour as `FluentBuilder.try_into_value_with`.
     */
-    pub fn try_into_value(self) -> TryIntoValue<TValue, Self> {
-        match self.inner {
+    pub fn try_into_value_with(self, ctx: &TContext) -> TryIntoValue<TValue, Self> {
+        match self.into_inner() {
             StatefulFluentBuilderInner {
                 state: State::Value(value),
                 mut fluent_method,
             } => TryIntoValue::Value(match fluent_method {
-                Some(ref mut fluent_method) => fluent_method.apply(value),
+                Some(ref mut fluent_method) => apply_method(fluent_method, ctx, value, Base::Value),
                 None => value,
             }),
             inner => TryIntoValue::Builder(StatefulFluentBuilder::new(inner)),
         }
     }
+
+    /**
+    Apply the fluent builder to an existing value, giving the context to any context-aware fluent methods.
+
+    This has the same behaviour as `FluentBuilder.apply_to_with`.
+    */
+    pub fn apply_to_with(self, ctx: &TContext, target: &mut TValue) {
+        let StatefulFluentBuilderInner {
+            state,
+            fluent_method,
+        } = self.into_inner();
+
+        match (state, fluent_method) {
+            (State::Value(value), Some(mut fluent_method)) => {
+                *target = apply_method(&mut fluent_method, ctx, value, Base::Value)
+            }
+            (State::Value(value), None) => *target = value,
+            (State::Seed(_), Some(mut fluent_method)) => replace_with(target, |value| {
+                apply_method(&mut fluent_method, ctx, value, Base::Target)
+            }),
+            (State::Seed(_), None) => (),
+        }
+    }
+
+    /**
+    Apply the fluent builder to an existing value, giving the context to any context-aware fluent methods
+    and using a function to construct a fallback for the target.
+
+    This has the same behaviour as `FluentBuilder.apply_to_with_or_else`.
+    */
+    pub fn apply_to_with_or_else<TDefault>(
+        self,
+        ctx: &TContext,
+        target: &mut TValue,
+        default_value: TDefault,
+    ) where
+        TDefault: FnOnce() -> TValue,
+    {
+        let StatefulFluentBuilderInner {
+            state,
+            fluent_method,
+        } = self.into_inner();
+
+        match (state, fluent_method) {
+            (State::Value(value), Some(mut fluent_method)) => {
+                *target = apply_method(&mut fluent_method, ctx, value, Base::Value)
+            }
+            (State::Value(value), None) => *target = value,
+            (State::Seed(_), Some(mut fluent_method)) => {
+                let value = mem::replace(target, default_value());
+                *target = apply_method(&mut fluent_method, ctx, value, Base::Target)
+            }
+            (State::Seed(_), None) => (),
+        }
+    }
+
+    /**
+    Get the value that fluent methods should be applied over, along with those methods.
+    */
+    fn into_base<TDefault>(
+        self,
+        default_value: TDefault,
+    ) -> (TValue, Base, Option<TStorage::Method>)
+    where
+        TDefault: FnOnce(TSeed) -> TValue,
+    {
+        let StatefulFluentBuilderInner {
+            state,
+            fluent_method,
+        } = self.into_inner();
+
+        match state {
+            State::Value(value) => (value, Base::Value, fluent_method),
+            State::Seed(seed) => (default_value(seed), Base::Default, fluent_method),
+        }
+    }
 }
 
-impl<TSeed, TValue, TStorage> StatefulFluentBuilder<TSeed, TValue, Stack, TStorage>
+impl<TSeed, TValue, TStorage, TContext: ?Sized>
+    StatefulFluentBuilder<TSeed, TValue, Stack, TStorage, TContext>
 where
-    TStorage: Storage<TValue>,
+    TStorage: Storage<TValue, TContext>,
 {
-    fn stack<TMethodStacker, TNextStorage>(
+    fn stack<TMethodStacker, TNextStorage, TNextContext: ?Sized>(
         self,
         fluent_stacker: TMethodStacker,
-    ) -> StatefulFluentBuilder<TSeed, TValue, Stack, TNextStorage>
+    ) -> StatefulFluentBuilder<TSeed, TValue, Stack, TNextStorage, TNextContext>
     where
         TMethodStacker: FnOnce(Option<TStorage::Method>) -> TNextStorage::Method,
-        TNextStorage: Storage<TValue>,
+        TNextStorage: Storage<TValue, TNextContext>,
     {
         let StatefulFluentBuilderInner {
             state,
             fluent_method: previous_fluent_method,
-        } = self.inner;
+        } = self.into_inner();
 
         let fluent_method = fluent_stacker(previous_fluent_method);
 
@@ -603,6 +1826,9 @@ where
     - a previous value, add the fluent method and retain that previous value.
     - a previous fluent method, stack this method on top and retain any previous value.
     */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
     pub fn fluent<TNextStorage>(
         self,
         seed: TSeed,
@@ -612,12 +1838,20 @@ where
         TValue,
         Stack,
         StatefulApply<TSeed, TValue, TStorage::Method, ByValue<TNextStorage>>,
+        TContext,
     >
     where
         TNextStorage: FnOnce(TSeed, TValue) -> TValue,
     {
+        let location = Location::caller();
+
         self.stack(move |previous_fluent_method| {
-            StatefulApply::new(seed, previous_fluent_method, ByValue(fluent_method))
+            StatefulApply::new(
+                seed,
+                previous_fluent_method,
+                ByValue(fluent_method),
+                location,
+            )
         })
     }
 
@@ -626,6 +1860,9 @@ where
 
     This method behaves the same as `fluent`, but mutates the value instead of replacing it.
     */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
     pub fn fluent_mut<TNextStorage>(
         self,
         seed: TSeed,
@@ -635,20 +1872,141 @@ where
         TValue,
         Stack,
         StatefulApply<TSeed, TValue, TStorage::Method, ByRefMut<TNextStorage>>,
+        TContext,
     >
     where
         TNextStorage: FnOnce(TSeed, &mut TValue),
     {
+        let location = Location::caller();
+
+        self.stack(move |previous_fluent_method| {
+            StatefulApply::new(
+                seed,
+                previous_fluent_method,
+                ByRefMut(fluent_method),
+                location,
+            )
+        })
+    }
+
+    /**
+    Stack a fluent method on the builder, using a clone of the builder's current seed.
+
+    This method behaves the same as `fluent`, but doesn't need to be given a seed.
+    The seed is cloned when this method is called, not when the value is built,
+    so changes made to the seed later using `fluent_seed` aren't seen by the method.
+
+    The method is only applied when the value is built from a seed.
+    If the builder contains a value instead of a seed then there's no seed to give it, so it's skipped.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn fluent_with_seed<TNextMethod>(
+        self,
+        fluent_method: TNextMethod,
+    ) -> StatefulFluentBuilder<
+        TSeed,
+        TValue,
+        Stack,
+        StatefulApply<
+            Option<TSeed>,
+            TValue,
+            TStorage::Method,
+            ByValue<impl FnOnce(Option<TSeed>, TValue) -> TValue>,
+        >,
+        TContext,
+    >
+    where
+        TSeed: Clone,
+        TNextMethod: FnOnce(TSeed, TValue) -> TValue,
+    {
+        let seed = self.seed().cloned();
+        let location = Location::caller();
+
+        self.stack(move |previous_fluent_method| {
+            StatefulApply::new(
+                seed,
+                previous_fluent_method,
+                ByValue(move |seed, value| match seed {
+                    Some(seed) => fluent_method(seed, value),
+                    None => value,
+                }),
+                location,
+            )
+        })
+    }
+
+    /**
+    Stack a fluent method on the builder, using a clone of the builder's current seed.
+
+    This method behaves the same as `fluent_with_seed`, but mutates the value instead of replacing it.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn fluent_mut_with_seed<TNextMethod>(
+        self,
+        fluent_method: TNextMethod,
+    ) -> StatefulFluentBuilder<
+        TSeed,
+        TValue,
+        Stack,
+        StatefulApply<
+            Option<TSeed>,
+            TValue,
+            TStorage::Method,
+            ByRefMut<impl FnOnce(Option<TSeed>, &mut TValue)>,
+        >,
+        TContext,
+    >
+    where
+        TSeed: Clone,
+        TNextMethod: FnOnce(TSeed, &mut TValue),
+    {
+        let seed = self.seed().cloned();
+        let location = Location::caller();
+
         self.stack(move |previous_fluent_method| {
-            StatefulApply::new(seed, previous_fluent_method, ByRefMut(fluent_method))
+            StatefulApply::new(
+                seed,
+                previous_fluent_method,
+                ByRefMut(move |seed, value: &mut TValue| {
+                    if let Some(seed) = seed {
+                        fluent_method(seed, value)
+                    }
+                }),
+                location,
+            )
         })
     }
 }
 
-impl<TSeed, TValue, TStorage> StatefulFluentBuilder<TSeed, TValue, Override, TStorage>
+impl<TSeed, TValue, TStorage, TContext: ?Sized>
+    StatefulFluentBuilder<TSeed, TValue, Override, TStorage, TContext>
 where
-    TStorage: Storage<TValue>,
+    TStorage: Storage<TValue, TContext>,
 {
+    fn with_fluent_method<TNextMethod, TNextContext: ?Sized>(
+        state: State<TSeed, TValue>,
+        fluent_method: TNextMethod,
+        location: &'static Location<'static>,
+    ) -> StatefulFluentBuilder<
+        TSeed,
+        TValue,
+        Override,
+        Apply<TValue, DefaultStorage, TNextMethod>,
+        TNextContext,
+    >
+    where
+        Apply<TValue, DefaultStorage, TNextMethod>: Method<TValue, TNextContext>,
+    {
+        StatefulFluentBuilder::new(StatefulFluentBuilderInner {
+            state,
+            fluent_method: Some(Apply::new(None, fluent_method, location)),
+        })
+    }
+
     /**
     Set the fluent method on the builder.
 
@@ -658,6 +2016,8 @@ where
     - a previous value, add the fluent method and remove that previous value.
     - a previous fluent method, that method will be replaced with the given one.
     */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
     pub fn fluent<TNextStorage>(
         self,
         seed: TSeed,
@@ -667,11 +2027,18 @@ where
         TValue,
         Override,
         Apply<TValue, DefaultStorage, ByValue<TNextStorage>>,
+        TContext,
     >
     where
         TNextStorage: FnOnce(TValue) -> TValue + 'static,
     {
-        StatefulFluentBuilder::<TSeed, TValue, Override, Inline>::from_fluent(seed, fluent_method)
+        self.discard();
+
+        Self::with_fluent_method(
+            State::Seed(seed),
+            ByValue(fluent_method),
+            Location::caller(),
+        )
     }
 
     /**
@@ -679,6 +2046,8 @@ where
 
     This method behaves the same as `fluent`, but mutates the value instead of replacing it.
     */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
     pub fn fluent_mut<TNextStorage>(
         self,
         seed: TSeed,
@@ -688,146 +2057,759 @@ where
         TValue,
         Override,
         Apply<TValue, DefaultStorage, ByRefMut<TNextStorage>>,
+        TContext,
     >
     where
         TNextStorage: FnOnce(&mut TValue) + 'static,
     {
-        StatefulFluentBuilder::<TSeed, TValue, Override, Inline>::from_fluent_mut(seed, fluent_method)
+        self.discard();
+
+        Self::with_fluent_method(
+            State::Seed(seed),
+            ByRefMut(fluent_method),
+            Location::caller(),
+        )
+    }
+
+    /**
+    Set the fluent method on the builder, keeping the builder's current seed.
+
+    This method behaves the same as `fluent`, but doesn't need to be given a seed.
+    The builder's current state is kept instead of being replaced,
+    so if the builder contains a value instead of a seed then the method is applied to that value.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
+    pub fn fluent_with_seed<TNextMethod>(
+        self,
+        fluent_method: TNextMethod,
+    ) -> StatefulFluentBuilder<
+        TSeed,
+        TValue,
+        Override,
+        Apply<TValue, DefaultStorage, ByValue<TNextMethod>>,
+        TContext,
+    >
+    where
+        TNextMethod: FnOnce(TValue) -> TValue + 'static,
+    {
+        // Any fluent methods are discarded, the same as `fluent`
+        let state = self.into_inner().state;
+
+        Self::with_fluent_method(state, ByValue(fluent_method), Location::caller())
+    }
+
+    /**
+    Set the fluent method on the builder, keeping the builder's current seed.
+
+    This method behaves the same as `fluent_with_seed`, but mutates the value instead of replacing it.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
+    pub fn fluent_mut_with_seed<TNextMethod>(
+        self,
+        fluent_method: TNextMethod,
+    ) -> StatefulFluentBuilder<
+        TSeed,
+        TValue,
+        Override,
+        Apply<TValue, DefaultStorage, ByRefMut<TNextMethod>>,
+        TContext,
+    >
+    where
+        TNextMethod: FnOnce(&mut TValue) + 'static,
+    {
+        let state = self.into_inner().state;
+
+        Self::with_fluent_method(state, ByRefMut(fluent_method), Location::caller())
+    }
+}
+
+impl<TSeed, TValue, TStack, TStorage, TContext: ?Sized>
+    StatefulFluentBuilder<TSeed, TValue, TStack, TStorage, TContext>
+where
+    TSeed: 'static,
+    TStorage: Storage<TValue, TContext>,
+    TStorage::Method: 'static,
+{
+    /**
+    Box a fluent builder so it can be easily captured as a field without generics.
+    */
+    pub fn boxed(self) -> StatefulFluentBuilder<TSeed, TValue, TStack, Boxed, TContext> {
+        let StatefulFluentBuilderInner {
+            state,
+            fluent_method,
+        } = self.into_inner();
+
+        let fluent_method = fluent_method.map(|f| BoxedMethod(vec![Box::new(f)]));
+
+        StatefulFluentBuilder::new(StatefulFluentBuilderInner {
+            state,
+            fluent_method,
+        })
+    }
+}
+
+impl<TSeed, TValue, TStack, TStorage, TContext: ?Sized>
+    StatefulFluentBuilder<TSeed, TValue, TStack, TStorage, TContext>
+where
+    TSeed: 'static,
+    TStorage: Storage<TValue, TContext>,
+    TStorage::Method: Send + 'static,
+{
+    /**
+    Box a fluent builder so it can be easily shared.
+    */
+    pub fn shared(self) -> StatefulFluentBuilder<TSeed, TValue, TStack, Shared, TContext> {
+        let StatefulFluentBuilderInner {
+            state,
+            fluent_method,
+        } = self.into_inner();
+
+        let fluent_method = fluent_method.map(|f| SharedMethod(vec![Box::new(f)]));
+
+        StatefulFluentBuilder::new(StatefulFluentBuilderInner {
+            state,
+            fluent_method,
+        })
+    }
+}
+
+impl<TValue, TContext: ?Sized, TFluent> Method<TValue, TContext> for TFluent
+where
+    TFluent: FnMut(TValue) -> TValue,
+{
+    fn apply(&mut self, _: &TContext, value: TValue) -> TValue {
+        self(value)
+    }
+}
+
+impl<TValue, TContext: ?Sized> Method<TValue, TContext> for BoxedMethod<TValue, TContext> {
+    fn apply(&mut self, ctx: &TContext, value: TValue) -> TValue {
+        if self.has_around() {
+            return self.apply_then(ctx, value, &mut Direct, &mut |value, _| value);
+        }
+
+        self.0.iter_mut().fold(value, |value, fluent_method| {
+            fluent_method.apply(ctx, value)
+        })
+    }
+
+    fn apply_steps(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+    ) -> TValue {
+        if self.has_around() {
+            return self.apply_then(ctx, value, steps, &mut |value, _| value);
+        }
+
+        self.0.iter_mut().fold(value, |value, fluent_method| {
+            fluent_method.apply_steps(ctx, value, steps)
+        })
+    }
+
+    fn apply_then(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        apply_sequence(&mut self.0, ctx, value, steps, then)
+    }
+
+    fn has_around(&self) -> bool {
+        self.0
+            .iter()
+            .any(|fluent_method| fluent_method.has_around())
     }
+
+    fn set_label(&mut self, label: &'static str) {
+        if let Some(fluent_method) = self.0.last_mut() {
+            fluent_method.set_label(label)
+        }
+    }
+}
+
+impl<TValue, TContext: ?Sized> Method<TValue, TContext> for SharedMethod<TValue, TContext> {
+    fn apply(&mut self, ctx: &TContext, value: TValue) -> TValue {
+        if self.has_around() {
+            return self.apply_then(ctx, value, &mut Direct, &mut |value, _| value);
+        }
+
+        self.0.iter_mut().fold(value, |value, fluent_method| {
+            fluent_method.apply(ctx, value)
+        })
+    }
+
+    fn apply_steps(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+    ) -> TValue {
+        if self.has_around() {
+            return self.apply_then(ctx, value, steps, &mut |value, _| value);
+        }
+
+        self.0.iter_mut().fold(value, |value, fluent_method| {
+            fluent_method.apply_steps(ctx, value, steps)
+        })
+    }
+
+    fn apply_then(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        apply_sequence(&mut self.0, ctx, value, steps, then)
+    }
+
+    fn has_around(&self) -> bool {
+        self.0
+            .iter()
+            .any(|fluent_method| fluent_method.has_around())
+    }
+
+    fn set_label(&mut self, label: &'static str) {
+        if let Some(fluent_method) = self.0.last_mut() {
+            fluent_method.set_label(label)
+        }
+    }
+}
+
+impl<TValue, TContext: ?Sized> Method<TValue, TContext> for Inline {
+    fn apply(&mut self, _: &TContext, value: TValue) -> TValue {
+        value
+    }
+
+    fn apply_steps(&mut self, _: &TContext, value: TValue, _: &mut dyn Steps<TValue>) -> TValue {
+        value
+    }
+
+    fn apply_then(
+        &mut self,
+        _: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        then(value, steps)
+    }
+}
+
+impl<TValue, TContext: ?Sized> Replay<TValue, TContext> for Inline {
+    fn replay_then(
+        &self,
+        _: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        then(value, steps)
+    }
+}
+
+/* pub(crate) items */
+
+/**
+The source a value was taken from before fluent methods were applied to it.
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Base {
+    Value,
+    Default,
+    Target,
+}
+
+impl fmt::Display for Base {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Base::Value => f.write_str("builder value"),
+            Base::Default => f.write_str("default value"),
+            Base::Target => f.write_str("target value"),
+        }
+    }
+}
+
+/**
+Replace a value in-place with the result of a function that takes it by-value.
+
+The target would be left without a value if the function panicked, so the process is aborted instead.
+*/
+fn replace_with<TValue, TReplace>(target: &mut TValue, replace: TReplace)
+where
+    TReplace: FnOnce(TValue) -> TValue,
+{
+    struct AbortOnUnwind;
+
+    impl Drop for AbortOnUnwind {
+        fn drop(&mut self) {
+            process::abort()
+        }
+    }
+
+    let guard = AbortOnUnwind;
+
+    // The value is only ever moved out of the target while `replace` runs,
+    // and if it unwinds then the guard aborts before the target can be observed again
+    unsafe {
+        let value = ptr::read(target);
+        ptr::write(target, replace(value));
+    }
+
+    mem::forget(guard);
+}
+
+/**
+Decide what to keep from a builder when a fluent method is added to it using its stack mode.
+
+The fluent method is returned if it should be stored, or `None` if the stack mode applied it straight away.
+*/
+fn stack_method<TStack, TValue, TPreviousMethod, TNextMethod>(
+    value: Option<TValue>,
+    previous_fluent_method: Option<TPreviousMethod>,
+    fluent_method: TNextMethod,
+    location: &'static Location<'static>,
+) -> (Option<TValue>, Option<TPreviousMethod>, Option<TNextMethod>)
+where
+    TStack: StackMode,
+    TNextMethod: Immediate<TValue>,
+{
+    // The method is only taken if the stack mode applies it straight away
+    let mut fluent_method = Some(fluent_method);
+    let (value, previous_fluent_method) = TStack::stack(
+        value,
+        previous_fluent_method,
+        NextMethod::new(&mut |value| {
+            let next = fluent_method.take().expect("attempted to re-use builder");
+
+            next.apply_immediately(value, location)
+                .map_err(|(next, value)| {
+                    fluent_method = Some(next);
+                    value
+                })
+        }),
+    );
+
+    (value, previous_fluent_method, fluent_method)
 }
 
-impl<TSeed, TValue, TStack, TStorage> StatefulFluentBuilder<TSeed, TValue, TStack, TStorage>
+/**
+Apply each method in a sequence, followed by `then`.
+
+Methods are applied in turn until one of them was added using `fluent_around`,
+which is given the rest of the sequence to wrap.
+*/
+fn apply_sequence<TValue, TContext: ?Sized, TMethod>(
+    mut fluent_methods: &mut [Box<TMethod>],
+    ctx: &TContext,
+    mut value: TValue,
+    steps: &mut dyn Steps<TValue>,
+    then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+) -> TValue
 where
-    TSeed: 'static,
-    TStorage: Storage<TValue>,
-    TStorage::Method: 'static,
+    TMethod: Method<TValue, TContext> + ?Sized,
 {
-    /**
-    Box a fluent builder so it can be easily captured as a field without generics.
-    */
-    pub fn boxed(self) -> BoxedStatefulFluentBuilder<TSeed, TValue, TStack> {
-        let StatefulFluentBuilderInner {
-            state,
-            fluent_method,
-        } = self.inner;
-
-        let fluent_method = fluent_method.map(|f| BoxedMethod(Box::new(f)));
+    while let Some((fluent_method, rest)) = fluent_methods.split_first_mut() {
+        if fluent_method.has_around() {
+            return fluent_method.apply_then(ctx, value, steps, &mut |value, steps| {
+                apply_sequence(rest, ctx, value, steps, then)
+            });
+        }
 
-        StatefulFluentBuilder::new(StatefulFluentBuilderInner {
-            state,
-            fluent_method,
-        })
+        value = fluent_method.apply_steps(ctx, value, steps);
+        fluent_methods = rest;
     }
+
+    then(value, steps)
 }
 
-impl<TSeed, TValue, TStack, TStorage> StatefulFluentBuilder<TSeed, TValue, TStack, TStorage>
+/**
+Apply a fluent method to a value.
+
+If the `log` feature is enabled then each step is applied individually so it can be logged.
+*/
+fn apply_method<TValue, TContext: ?Sized, TMethod>(
+    fluent_method: &mut TMethod,
+    ctx: &TContext,
+    value: TValue,
+    base: Base,
+) -> TValue
 where
-    TSeed: 'static,
-    TStorage: Storage<TValue>,
-    TStorage::Method: Send + 'static,
+    TMethod: Method<TValue, TContext>,
 {
-    /**
-    Box a fluent builder so it can be easily shared.
-    */
-    pub fn shared(self) -> SharedStatefulFluentBuilder<TSeed, TValue, TStack> {
-        let StatefulFluentBuilderInner {
-            state,
-            fluent_method,
-        } = self.inner;
-
-        let fluent_method = fluent_method.map(|f| SharedMethod(Box::new(f)));
-
-        StatefulFluentBuilder::new(StatefulFluentBuilderInner {
-            state,
-            fluent_method,
-        })
+    #[cfg(feature = "log")]
+    {
+        if logging::enabled() {
+            return fluent_method.apply_steps(ctx, value, &mut logging::LogSteps::new(base));
+        }
     }
+
+    let _ = base;
+    fluent_method.apply(ctx, value)
 }
 
-impl<TValue, TFluent> Method<TValue> for TFluent
+/**
+Apply a fluent method to a value without consuming it.
+
+If the `log` feature is enabled then each step is logged, the same as `apply_method`.
+*/
+fn replay_method<TValue, TContext: ?Sized, TMethod>(
+    fluent_method: &TMethod,
+    ctx: &TContext,
+    value: TValue,
+    base: Base,
+) -> TValue
 where
-    TFluent: FnMut(TValue) -> TValue,
+    TMethod: Replay<TValue, TContext>,
 {
-    fn apply(&mut self, value: TValue) -> TValue {
-        self(value)
+    #[cfg(feature = "log")]
+    {
+        if logging::enabled() {
+            return fluent_method.replay_then(
+                ctx,
+                value,
+                &mut logging::LogSteps::new(base),
+                &mut |value, _| value,
+            );
+        }
     }
+
+    let _ = base;
+    fluent_method.replay_then(ctx, value, &mut Direct, &mut |value, _| value)
+}
+
+/**
+A single fluent method in a chain, along with where it came from.
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Step {
+    label: Option<&'static str>,
+    location: Option<&'static Location<'static>>,
 }
 
-impl<TValue> Method<TValue> for BoxedMethod<TValue> {
-    fn apply(&mut self, value: TValue) -> TValue {
-        self.0.apply(value)
+impl Step {
+    fn new(location: &'static Location<'static>) -> Self {
+        Step {
+            label: None,
+            location: Some(location),
+        }
+    }
+
+    fn unknown() -> Self {
+        Step {
+            label: None,
+            location: None,
+        }
+    }
+
+    pub fn label(&self) -> Option<&'static str> {
+        self.label
+    }
+
+    pub fn location(&self) -> Option<&'static Location<'static>> {
+        self.location
     }
 }
 
-impl<TValue> Method<TValue> for SharedMethod<TValue> {
-    fn apply(&mut self, value: TValue) -> TValue {
-        self.0.apply(value)
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.label, self.location) {
+            (Some(label), _) => f.write_str(label),
+            (None, Some(location)) => fmt::Display::fmt(location, f),
+            (None, None) => f.write_str("<unknown>"),
+        }
     }
 }
 
-impl<TValue> Method<TValue> for Inline {
-    fn apply(&mut self, value: TValue) -> TValue {
-        value
+/**
+A visitor that's responsible for applying each step in a fluent method.
+
+The `apply` function must be called exactly once, and is given the visitor back
+so any steps that are applied within it are also visited.
+*/
+pub trait Steps<TValue> {
+    fn step(
+        &mut self,
+        step: Step,
+        value: TValue,
+        apply: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue;
+}
+
+/**
+Apply each step without observing it.
+*/
+struct Direct;
+
+impl<TValue> Steps<TValue> for Direct {
+    fn step(
+        &mut self,
+        _: Step,
+        value: TValue,
+        apply: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        apply(value, self)
     }
 }
 
-/* pub(crate) items */
+pub trait Method<TValue, TContext: ?Sized = ()> {
+    fn apply(&mut self, ctx: &TContext, value: TValue) -> TValue;
+
+    fn apply_steps(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+    ) -> TValue {
+        steps.step(Step::unknown(), value, &mut |value, _| {
+            self.apply(ctx, value)
+        })
+    }
+
+    /**
+    Apply the method, along with `then`, which applies the methods that were added after it.
+
+    Most methods are applied in sequence, before `then`.
+    Methods added using `fluent_around` wrap `then` instead, so they can run code after it returns.
+    */
+    fn apply_then(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        let value = self.apply_steps(ctx, value, steps);
+        then(value, steps)
+    }
+
+    /**
+    Whether the method, or any method before it, was added using `fluent_around`.
+
+    Chains without an around method are applied directly, instead of passing the methods after them to `apply_then`.
+    */
+    fn has_around(&self) -> bool {
+        false
+    }
+
+    fn set_label(&mut self, label: &'static str) {
+        let _ = label;
+    }
+}
+
+/**
+A fluent method that can be applied any number of times through a shared reference.
+*/
+pub trait Replay<TValue, TContext: ?Sized = ()>: Method<TValue, TContext> {
+    /**
+    Apply the method without consuming it, along with `then`, which applies the methods that were added after it.
 
-pub trait Method<TValue> {
-    fn apply(&mut self, value: TValue) -> TValue;
+    This has the same behaviour as `Method.apply_then`.
+    */
+    fn replay_then(
+        &self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue;
 }
 
 pub struct ByValue<TFluent>(TFluent);
 
 pub struct ByRefMut<TFluent>(TFluent);
 
+pub struct Around<TFluent>(TFluent);
+
 pub struct Apply<TValue, TPreviousMethod, TNextMethod> {
     inner: Option<StatefulApply<(), TValue, TPreviousMethod, TNextMethod>>,
 }
 
 impl<TValue, TPreviousMethod, TNextMethod> Apply<TValue, TPreviousMethod, TNextMethod> {
-    fn new(previous: Option<TPreviousMethod>, next: TNextMethod) -> Self {
+    fn new(
+        previous: Option<TPreviousMethod>,
+        next: TNextMethod,
+        location: &'static Location<'static>,
+    ) -> Self {
         Apply {
-            inner: Some(StatefulApply::new((), previous, next)),
+            inner: Some(StatefulApply::new((), previous, next, location)),
+        }
+    }
+
+    fn set_label(&mut self, label: &'static str) {
+        if let Some(ref mut inner) = self.inner {
+            inner.set_label(label);
         }
     }
 }
 
-impl<TValue, TPreviousMethod, TNextMethod> Method<TValue>
+impl<TValue, TContext: ?Sized, TPreviousMethod, TNextMethod> Method<TValue, TContext>
     for Apply<TValue, TPreviousMethod, ByValue<TNextMethod>>
 where
-    TPreviousMethod: Method<TValue>,
+    TPreviousMethod: Method<TValue, TContext>,
     TNextMethod: FnOnce(TValue) -> TValue,
 {
-    fn apply(&mut self, value: TValue) -> TValue {
-        use std::mem;
-        let inner = mem::replace(&mut self.inner, None).expect("attempted to re-use builder");
+    fn apply(&mut self, ctx: &TContext, value: TValue) -> TValue {
+        let inner = self.inner.take().expect("attempted to re-use builder");
 
         let (next, inner) = inner.take_next();
 
         inner
             .set_next(ByValue(move |_, value: TValue| (next.0)(value)))
-            .apply(value)
+            .apply(ctx, value)
+    }
+
+    fn apply_steps(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+    ) -> TValue {
+        let inner = self.inner.take().expect("attempted to re-use builder");
+
+        let (next, inner) = inner.take_next();
+
+        inner
+            .set_next(ByValue(move |_, value: TValue| (next.0)(value)))
+            .apply_steps(ctx, value, steps)
+    }
+
+    fn apply_then(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        let inner = self.inner.take().expect("attempted to re-use builder");
+
+        let (next, inner) = inner.take_next();
+
+        inner
+            .set_next(ByValue(move |_, value: TValue| (next.0)(value)))
+            .apply_then(ctx, value, steps, then)
+    }
+
+    fn has_around(&self) -> bool {
+        self.inner
+            .as_ref()
+            .is_some_and(|inner| inner.previous_has_around::<TContext>())
+    }
+
+    fn set_label(&mut self, label: &'static str) {
+        Apply::set_label(self, label)
     }
 }
 
-impl<TValue, TPreviousMethod, TNextMethod> Method<TValue>
+impl<TValue, TContext: ?Sized, TPreviousMethod, TNextMethod> Method<TValue, TContext>
     for Apply<TValue, TPreviousMethod, ByRefMut<TNextMethod>>
 where
-    TPreviousMethod: Method<TValue>,
+    TPreviousMethod: Method<TValue, TContext>,
     TNextMethod: FnOnce(&mut TValue),
 {
-    fn apply(&mut self, value: TValue) -> TValue {
-        use std::mem;
-        let inner = mem::replace(&mut self.inner, None).expect("attempted to re-use builder");
+    fn apply(&mut self, ctx: &TContext, value: TValue) -> TValue {
+        let inner = self.inner.take().expect("attempted to re-use builder");
+
+        let (next, inner) = inner.take_next();
+
+        inner
+            .set_next(ByRefMut(move |_, value: &mut TValue| (next.0)(value)))
+            .apply(ctx, value)
+    }
+
+    fn apply_steps(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+    ) -> TValue {
+        let inner = self.inner.take().expect("attempted to re-use builder");
+
+        let (next, inner) = inner.take_next();
+
+        inner
+            .set_next(ByRefMut(move |_, value: &mut TValue| (next.0)(value)))
+            .apply_steps(ctx, value, steps)
+    }
+
+    fn apply_then(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        let inner = self.inner.take().expect("attempted to re-use builder");
+
+        let (next, inner) = inner.take_next();
+
+        inner
+            .set_next(ByRefMut(move |_, value: &mut TValue| (next.0)(value)))
+            .apply_then(ctx, value, steps, then)
+    }
+
+    fn has_around(&self) -> bool {
+        self.inner
+            .as_ref()
+            .is_some_and(|inner| inner.previous_has_around::<TContext>())
+    }
+
+    fn set_label(&mut self, label: &'static str) {
+        Apply::set_label(self, label)
+    }
+}
+
+impl<TValue, TContext: ?Sized, TPreviousMethod, TNextMethod> Method<TValue, TContext>
+    for Apply<TValue, TPreviousMethod, Around<TNextMethod>>
+where
+    TPreviousMethod: Method<TValue, TContext>,
+    TNextMethod: FnOnce(TValue, &mut dyn FnMut(TValue) -> TValue) -> TValue,
+{
+    fn apply(&mut self, ctx: &TContext, value: TValue) -> TValue {
+        self.apply_steps(ctx, value, &mut Direct)
+    }
+
+    fn apply_steps(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+    ) -> TValue {
+        self.apply_then(ctx, value, steps, &mut |value, _| value)
+    }
+
+    fn apply_then(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        let inner = self.inner.take().expect("attempted to re-use builder");
 
         let (next, inner) = inner.take_next();
 
         inner
-            .set_next(ByRefMut(move |_, mut value: &mut TValue| {
-                (next.0)(&mut value)
-            }))
-            .apply(value)
+            .set_next(Around(
+                move |_, value: TValue, apply_next: &mut dyn FnMut(TValue) -> TValue| {
+                    (next.0)(value, apply_next)
+                },
+            ))
+            .apply_then(ctx, value, steps, then)
+    }
+
+    fn has_around(&self) -> bool {
+        true
+    }
+
+    fn set_label(&mut self, label: &'static str) {
+        Apply::set_label(self, label)
     }
 }
 
@@ -835,21 +2817,32 @@ pub struct StatefulApply<TSeed, TValue, TPreviousMethod, TNextMethod> {
     seed: Option<TSeed>,
     previous: Option<TPreviousMethod>,
     next: Option<TNextMethod>,
+    step: Step,
     _marker: PhantomData<TValue>,
 }
 
 impl<TSeed, TValue, TPreviousMethod, TNextMethod>
     StatefulApply<TSeed, TValue, TPreviousMethod, TNextMethod>
 {
-    fn new(seed: TSeed, previous: Option<TPreviousMethod>, next: TNextMethod) -> Self {
+    fn new(
+        seed: TSeed,
+        previous: Option<TPreviousMethod>,
+        next: TNextMethod,
+        location: &'static Location<'static>,
+    ) -> Self {
         StatefulApply {
             seed: Some(seed),
-            previous: previous,
+            previous,
             next: Some(next),
+            step: Step::new(location),
             _marker: PhantomData,
         }
     }
 
+    fn set_label(&mut self, label: &'static str) {
+        self.step.label = Some(label);
+    }
+
     fn take_next(
         self,
     ) -> (
@@ -861,12 +2854,22 @@ impl<TSeed, TValue, TPreviousMethod, TNextMethod>
             seed: self.seed,
             previous: self.previous,
             next: Some(()),
+            step: self.step,
             _marker: PhantomData,
         };
 
         (next, self_sans_next)
     }
 
+    fn previous_has_around<TContext: ?Sized>(&self) -> bool
+    where
+        TPreviousMethod: Method<TValue, TContext>,
+    {
+        self.previous
+            .as_ref()
+            .is_some_and(Method::<TValue, TContext>::has_around)
+    }
+
     fn set_next<TNewNextMethod>(
         self,
         next: TNewNextMethod,
@@ -875,80 +2878,442 @@ impl<TSeed, TValue, TPreviousMethod, TNextMethod>
             seed: self.seed,
             previous: self.previous,
             next: Some(next),
+            step: self.step,
             _marker: PhantomData,
         }
     }
 }
 
-impl<TSeed, TValue, TPreviousMethod, TNextMethod> Method<TValue>
+/**
+Apply the previous methods, followed by `then`.
+
+Methods are applied in continuation-passing style, so each method decides where the ones after it are applied.
+*/
+fn apply_previous<TValue, TContext: ?Sized, TPreviousMethod>(
+    previous: Option<&mut TPreviousMethod>,
+    ctx: &TContext,
+    value: TValue,
+    steps: &mut dyn Steps<TValue>,
+    then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+) -> TValue
+where
+    TPreviousMethod: Method<TValue, TContext>,
+{
+    match previous {
+        Some(previous) => previous.apply_then(ctx, value, steps, then),
+        None => then(value, steps),
+    }
+}
+
+impl<TSeed, TValue, TContext: ?Sized, TPreviousMethod, TNextMethod> Method<TValue, TContext>
     for StatefulApply<TSeed, TValue, TPreviousMethod, ByValue<TNextMethod>>
 where
-    TPreviousMethod: Method<TValue>,
+    TPreviousMethod: Method<TValue, TContext>,
     TNextMethod: FnOnce(TSeed, TValue) -> TValue,
 {
-    fn apply(&mut self, value: TValue) -> TValue {
-        use std::mem;
-        let seed = mem::replace(&mut self.seed, None).expect("attempted to re-use builder");
-        let next = mem::replace(&mut self.next, None).expect("attempted to re-use builder");
+    fn apply(&mut self, ctx: &TContext, value: TValue) -> TValue {
+        if self.has_around() {
+            return self.apply_then(ctx, value, &mut Direct, &mut |value, _| value);
+        }
+
+        let seed = self.seed.take().expect("attempted to re-use builder");
+        let next = self.next.take().expect("attempted to re-use builder");
 
         let value = match self.previous {
-            Some(ref mut previous) => previous.apply(value),
+            Some(ref mut previous) => previous.apply(ctx, value),
             None => value,
         };
 
         (next.0)(seed, value)
     }
+
+    fn apply_steps(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+    ) -> TValue {
+        if self.has_around() {
+            return self.apply_then(ctx, value, steps, &mut |value, _| value);
+        }
+
+        let seed = self.seed.take().expect("attempted to re-use builder");
+        let next = self.next.take().expect("attempted to re-use builder");
+
+        let value = match self.previous {
+            Some(ref mut previous) => previous.apply_steps(ctx, value, steps),
+            None => value,
+        };
+
+        let mut next = Some((seed, next));
+        steps.step(self.step, value, &mut |value, _| {
+            let (seed, next) = next.take().expect("attempted to re-use builder");
+            (next.0)(seed, value)
+        })
+    }
+
+    fn apply_then(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        let seed = self.seed.take().expect("attempted to re-use builder");
+        let next = self.next.take().expect("attempted to re-use builder");
+        let step = self.step;
+
+        let mut next = Some((seed, next));
+        apply_previous(
+            self.previous.as_mut(),
+            ctx,
+            value,
+            steps,
+            &mut |value, steps| {
+                let value = steps.step(step, value, &mut |value, _| {
+                    let (seed, next) = next.take().expect("attempted to re-use builder");
+                    (next.0)(seed, value)
+                });
+
+                then(value, steps)
+            },
+        )
+    }
+
+    fn has_around(&self) -> bool {
+        self.previous_has_around::<TContext>()
+    }
+
+    fn set_label(&mut self, label: &'static str) {
+        StatefulApply::set_label(self, label)
+    }
+}
+
+impl<TSeed, TValue, TContext: ?Sized, TPreviousMethod, TNextMethod> Method<TValue, TContext>
+    for StatefulApply<TSeed, TValue, TPreviousMethod, ByRefMut<TNextMethod>>
+where
+    TPreviousMethod: Method<TValue, TContext>,
+    TNextMethod: FnOnce(TSeed, &mut TValue),
+{
+    fn apply(&mut self, ctx: &TContext, value: TValue) -> TValue {
+        if self.has_around() {
+            return self.apply_then(ctx, value, &mut Direct, &mut |value, _| value);
+        }
+
+        let seed = self.seed.take().expect("attempted to re-use builder");
+        let next = self.next.take().expect("attempted to re-use builder");
+
+        let value = match self.previous {
+            Some(ref mut previous) => previous.apply(ctx, value),
+            None => value,
+        };
+
+        let mut value = value;
+        (next.0)(seed, &mut value);
+        value
+    }
+
+    fn apply_steps(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+    ) -> TValue {
+        if self.has_around() {
+            return self.apply_then(ctx, value, steps, &mut |value, _| value);
+        }
+
+        let seed = self.seed.take().expect("attempted to re-use builder");
+        let next = self.next.take().expect("attempted to re-use builder");
+
+        let value = match self.previous {
+            Some(ref mut previous) => previous.apply_steps(ctx, value, steps),
+            None => value,
+        };
+
+        let mut next = Some((seed, next));
+        steps.step(self.step, value, &mut |value, _| {
+            let (seed, next) = next.take().expect("attempted to re-use builder");
+            let mut value = value;
+            (next.0)(seed, &mut value);
+            value
+        })
+    }
+
+    fn apply_then(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        let seed = self.seed.take().expect("attempted to re-use builder");
+        let next = self.next.take().expect("attempted to re-use builder");
+        let step = self.step;
+
+        let mut next = Some((seed, next));
+        apply_previous(
+            self.previous.as_mut(),
+            ctx,
+            value,
+            steps,
+            &mut |value, steps| {
+                let value = steps.step(step, value, &mut |mut value, _| {
+                    let (seed, next) = next.take().expect("attempted to re-use builder");
+                    (next.0)(seed, &mut value);
+                    value
+                });
+
+                then(value, steps)
+            },
+        )
+    }
+
+    fn has_around(&self) -> bool {
+        self.previous_has_around::<TContext>()
+    }
+
+    fn set_label(&mut self, label: &'static str) {
+        StatefulApply::set_label(self, label)
+    }
+}
+
+impl<TSeed, TValue, TContext: ?Sized, TPreviousMethod, TNextMethod> Method<TValue, TContext>
+    for StatefulApply<TSeed, TValue, TPreviousMethod, Around<TNextMethod>>
+where
+    TPreviousMethod: Method<TValue, TContext>,
+    TNextMethod: FnOnce(TSeed, TValue, &mut dyn FnMut(TValue) -> TValue) -> TValue,
+{
+    fn apply(&mut self, ctx: &TContext, value: TValue) -> TValue {
+        self.apply_steps(ctx, value, &mut Direct)
+    }
+
+    fn apply_steps(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+    ) -> TValue {
+        self.apply_then(ctx, value, steps, &mut |value, _| value)
+    }
+
+    fn apply_then(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        let seed = self.seed.take().expect("attempted to re-use builder");
+        let next = self.next.take().expect("attempted to re-use builder");
+        let step = self.step;
+
+        // The later methods are applied within this step, instead of after it
+        let mut next = Some((seed, next));
+        apply_previous(
+            self.previous.as_mut(),
+            ctx,
+            value,
+            steps,
+            &mut |value, steps| {
+                steps.step(step, value, &mut |value, steps| {
+                    let (seed, next) = next.take().expect("attempted to re-use builder");
+                    (next.0)(seed, value, &mut |value| then(value, steps))
+                })
+            },
+        )
+    }
+
+    fn has_around(&self) -> bool {
+        true
+    }
+
+    fn set_label(&mut self, label: &'static str) {
+        StatefulApply::set_label(self, label)
+    }
+}
+
+/**
+Replay the previous methods, followed by `then`.
+
+This has the same behaviour as `apply_previous`, but the methods aren't consumed.
+*/
+fn replay_previous<TValue, TContext: ?Sized, TPreviousMethod>(
+    previous: Option<&TPreviousMethod>,
+    ctx: &TContext,
+    value: TValue,
+    steps: &mut dyn Steps<TValue>,
+    then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+) -> TValue
+where
+    TPreviousMethod: Replay<TValue, TContext>,
+{
+    match previous {
+        Some(previous) => previous.replay_then(ctx, value, steps, then),
+        None => then(value, steps),
+    }
+}
+
+impl<TValue, TContext: ?Sized, TPreviousMethod, TNextMethod> Replay<TValue, TContext>
+    for Apply<TValue, TPreviousMethod, ByValue<TNextMethod>>
+where
+    TPreviousMethod: Replay<TValue, TContext>,
+    TNextMethod: Fn(TValue) -> TValue,
+{
+    fn replay_then(
+        &self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        let inner = self.inner.as_ref().expect("attempted to re-use builder");
+        let next = inner.next.as_ref().expect("attempted to re-use builder");
+        let step = inner.step;
+
+        replay_previous(
+            inner.previous.as_ref(),
+            ctx,
+            value,
+            steps,
+            &mut |value, steps| {
+                let value = steps.step(step, value, &mut |value, _| (next.0)(value));
+
+                then(value, steps)
+            },
+        )
+    }
+}
+
+impl<TValue, TContext: ?Sized, TPreviousMethod, TNextMethod> Replay<TValue, TContext>
+    for Apply<TValue, TPreviousMethod, ByRefMut<TNextMethod>>
+where
+    TPreviousMethod: Replay<TValue, TContext>,
+    TNextMethod: Fn(&mut TValue),
+{
+    fn replay_then(
+        &self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        let inner = self.inner.as_ref().expect("attempted to re-use builder");
+        let next = inner.next.as_ref().expect("attempted to re-use builder");
+        let step = inner.step;
+
+        replay_previous(
+            inner.previous.as_ref(),
+            ctx,
+            value,
+            steps,
+            &mut |value, steps| {
+                let value = steps.step(step, value, &mut |mut value, _| {
+                    (next.0)(&mut value);
+                    value
+                });
+
+                then(value, steps)
+            },
+        )
+    }
+}
+
+impl<TSeed, TValue, TContext: ?Sized, TPreviousMethod, TNextMethod> Replay<TValue, TContext>
+    for StatefulApply<TSeed, TValue, TPreviousMethod, ByValue<TNextMethod>>
+where
+    TSeed: Clone,
+    TPreviousMethod: Replay<TValue, TContext>,
+    TNextMethod: Fn(TSeed, TValue) -> TValue,
+{
+    fn replay_then(
+        &self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        let seed = self.seed.as_ref().expect("attempted to re-use builder");
+        let next = self.next.as_ref().expect("attempted to re-use builder");
+        let step = self.step;
+
+        replay_previous(
+            self.previous.as_ref(),
+            ctx,
+            value,
+            steps,
+            &mut |value, steps| {
+                let value = steps.step(step, value, &mut |value, _| (next.0)(seed.clone(), value));
+
+                then(value, steps)
+            },
+        )
+    }
 }
 
-impl<TSeed, TValue, TPreviousMethod, TNextMethod> Method<TValue>
+impl<TSeed, TValue, TContext: ?Sized, TPreviousMethod, TNextMethod> Replay<TValue, TContext>
     for StatefulApply<TSeed, TValue, TPreviousMethod, ByRefMut<TNextMethod>>
 where
-    TPreviousMethod: Method<TValue>,
-    TNextMethod: FnOnce(TSeed, &mut TValue),
+    TSeed: Clone,
+    TPreviousMethod: Replay<TValue, TContext>,
+    TNextMethod: Fn(TSeed, &mut TValue),
 {
-    fn apply(&mut self, value: TValue) -> TValue {
-        use std::mem;
-        let seed = mem::replace(&mut self.seed, None).expect("attempted to re-use builder");
-        let next = mem::replace(&mut self.next, None).expect("attempted to re-use builder");
-
-        let mut value = match self.previous {
-            Some(ref mut previous) => previous.apply(value),
-            None => value,
-        };
+    fn replay_then(
+        &self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        let seed = self.seed.as_ref().expect("attempted to re-use builder");
+        let next = self.next.as_ref().expect("attempted to re-use builder");
+        let step = self.step;
+
+        replay_previous(
+            self.previous.as_ref(),
+            ctx,
+            value,
+            steps,
+            &mut |value, steps| {
+                let value = steps.step(step, value, &mut |mut value, _| {
+                    (next.0)(seed.clone(), &mut value);
+                    value
+                });
 
-        (next.0)(seed, &mut value);
-        value
+                then(value, steps)
+            },
+        )
     }
 }
 
-pub trait Storage<TValue> {
-    type Method: Method<TValue>;
+pub trait Storage<TValue, TContext: ?Sized = ()> {
+    type Method: Method<TValue, TContext>;
 }
 
-impl<TValue> Storage<TValue> for Boxed {
-    type Method = BoxedMethod<TValue>;
+impl<TValue, TContext: ?Sized> Storage<TValue, TContext> for Boxed {
+    type Method = BoxedMethod<TValue, TContext>;
 }
 
-impl<TValue> Storage<TValue> for Shared {
-    type Method = SharedMethod<TValue>;
+impl<TValue, TContext: ?Sized> Storage<TValue, TContext> for Shared {
+    type Method = SharedMethod<TValue, TContext>;
 }
 
-impl<TValue> Storage<TValue> for Inline {
+impl<TValue, TContext: ?Sized> Storage<TValue, TContext> for Inline {
     type Method = Self;
 }
 
-impl<TValue, TPreviousMethod, TNextMethod> Storage<TValue>
+impl<TValue, TContext: ?Sized, TPreviousMethod, TNextMethod> Storage<TValue, TContext>
     for Apply<TValue, TPreviousMethod, TNextMethod>
 where
-    Apply<TValue, TPreviousMethod, TNextMethod>: Method<TValue>,
+    Apply<TValue, TPreviousMethod, TNextMethod>: Method<TValue, TContext>,
 {
     type Method = Self;
 }
 
-impl<TSeed, TValue, TPreviousMethod, TNextMethod> Storage<TValue>
+impl<TSeed, TValue, TContext: ?Sized, TPreviousMethod, TNextMethod> Storage<TValue, TContext>
     for StatefulApply<TSeed, TValue, TPreviousMethod, TNextMethod>
 where
-    StatefulApply<TSeed, TValue, TPreviousMethod, TNextMethod>: Method<TValue>,
+    StatefulApply<TSeed, TValue, TPreviousMethod, TNextMethod>: Method<TValue, TContext>,
 {
     type Method = Self;
 }
@@ -1026,6 +3391,31 @@ mod tests {
 
                 assert_eq!("default_f1_f2", result);
             }
+
+            #[test]
+            fn default_fluent_apply_to() {
+                let mut target = "target".to_owned();
+
+                FluentBuilder::<String, Stack>::default()
+                    .fluent_mut(|v| v.push_str("_f1"))
+                    .fluent_mut(|v| v.push_str("_f2"))
+                    .boxed()
+                    .apply_to(&mut target);
+
+                assert_eq!("target_f1_f2", target);
+            }
+
+            #[test]
+            fn from_fn_into_boxed_fn() {
+                let f = FluentBuilder::<String, Stack, Boxed>::from_fn(|v| format!("{}_f1", v))
+                    .fluent_mut(|v| v.push_str("_f2"))
+                    .boxed()
+                    .into_boxed_fn();
+
+                let result = f("default".to_owned());
+
+                assert_eq!("default_f1_f2", result);
+            }
         }
     }
 
@@ -1084,164 +3474,573 @@ mod tests {
                     .value("value".to_owned())
                     .shared();
 
-                let result = builder.into_value(|| "default".to_owned());
+                let result = builder.into_value(|| "default".to_owned());
+
+                assert_eq!("value", result);
+            }
+
+            #[test]
+            fn default_fluent() {
+                let builder = FluentBuilder::<String, Stack>::default()
+                    .fluent_mut(|v| v.push_str("_f1"))
+                    .fluent_mut(|v| v.push_str("_f2"))
+                    .shared();
+
+                let result = builder.into_value(|| "default".to_owned());
+
+                assert_eq!("default_f1_f2", result);
+            }
+
+            #[test]
+            fn value_fluent_apply_to() {
+                let mut target = "target".to_owned();
+
+                FluentBuilder::<String, Stack>::default()
+                    .value("value".to_owned())
+                    .fluent_mut(|v| v.push_str("_f1"))
+                    .shared()
+                    .apply_to(&mut target);
+
+                assert_eq!("value_f1", target);
+            }
+        }
+    }
+
+    mod stateless {
+        mod fluent_override {
+            use imp::*;
+
+            #[test]
+            fn default() {
+                let builder = FluentBuilder::<String>::default();
+
+                let result = builder.into_value(|| "default".to_owned());
+
+                assert_eq!("default", result);
+            }
+
+            #[test]
+            fn default_value() {
+                let builder = FluentBuilder::<String>::default().value("value".to_owned());
+
+                let result = builder.into_value(|| "default".to_owned());
+
+                assert_eq!("value", result);
+            }
+
+            #[test]
+            fn default_fluent() {
+                let builder = FluentBuilder::<String>::default()
+                    .fluent_mut(|v| v.push_str("_f1"))
+                    .fluent_mut(|v| v.push_str("_f2"));
+
+                let result = builder.into_value(|| "default".to_owned());
+
+                assert_eq!("default_f2", result);
+            }
+
+            #[test]
+            fn default_fluent_keeps_type() {
+                let builder: FluentBuilder<String, Override, Apply<String, Inline, ByRefMut<_>>> =
+                    FluentBuilder::<String>::default()
+                        .fluent_mut(|v| v.push_str("_f1"))
+                        .fluent(|v| format!("{}_f2", v))
+                        .fluent_mut(|v| v.push_str("_f3"));
+
+                let result = builder.into_value(|| "default".to_owned());
+
+                assert_eq!("default_f3", result);
+            }
+
+            #[test]
+            fn default_value_fluent() {
+                let builder = FluentBuilder::<String>::default()
+                    .value("value".to_owned())
+                    .fluent_mut(|v| v.push_str("_f1"))
+                    .fluent_mut(|v| v.push_str("_f2"));
+
+                let result = builder.into_value(|| "default".to_owned());
+
+                assert_eq!("default_f2", result);
+            }
+
+            #[test]
+            fn default_value_apply_to() {
+                let mut target = "target".to_owned();
+
+                FluentBuilder::<String>::default()
+                    .value("value".to_owned())
+                    .apply_to(&mut target);
+
+                assert_eq!("value", target);
+            }
+
+            #[test]
+            fn default_fluent_apply_to() {
+                let mut target = "target".to_owned();
+
+                FluentBuilder::<String>::default()
+                    .fluent_mut(|v| v.push_str("_f1"))
+                    .fluent_mut(|v| v.push_str("_f2"))
+                    .apply_to(&mut target);
+
+                assert_eq!("target_f2", target);
+            }
+
+            #[test]
+            fn from_value() {
+                let builder = FluentBuilder::<String>::from("value".to_owned());
+
+                let result = builder.into_value(|| "default".to_owned());
+
+                assert_eq!("value", result);
+            }
+
+            #[test]
+            fn from_option() {
+                let some = FluentBuilder::<String>::from(Some("value".to_owned()));
+                let none = FluentBuilder::<String>::from(None);
+
+                assert_eq!("value", some.into_value(|| "default".to_owned()));
+                assert_eq!("default", none.into_value(|| "default".to_owned()));
+            }
+
+            #[test]
+            fn default_take_unwrap_or_else() {
+                let builder = FluentBuilder::<String>::default().fluent_mut(|v| v.push_str("_f1"));
+
+                let result = builder
+                    .try_into_value()
+                    .map_value(|_| panic!("expected `TryIntoValue::Builder`"))
+                    .unwrap_or_else(|builder| builder.into_value(|| "default".to_owned()));
+
+                assert_eq!("default_f1", result);
+            }
+
+            #[test]
+            fn value_take_into_result() {
+                let builder = FluentBuilder::<String>::default().value("value".to_owned());
+
+                let result = builder
+                    .try_into_value()
+                    .map_value(|v| v.len())
+                    .into_result();
+
+                assert_eq!(Some(5), result.ok());
+            }
+
+            #[test]
+            fn value_take() {
+                let builder = FluentBuilder::<String>::default().value("value".to_owned());
+
+                let result = match builder.try_into_value() {
+                    TryIntoValue::Value(value) => value,
+                    _ => panic!("expected `TryIntoValue::Value`"),
+                };
+
+                assert_eq!("value", result);
+            }
+
+            #[test]
+            fn default_fluent_value() {
+                let builder = FluentBuilder::<String>::default()
+                    .fluent_mut(|v| v.push_str("_f1"))
+                    .fluent_mut(|v| v.push_str("_f2"))
+                    .value("value".to_owned());
+
+                let result = builder.into_value(|| "default".to_owned());
+
+                assert_eq!("value", result);
+            }
+        }
+
+        mod fluent_stack {
+            use imp::*;
+
+            #[test]
+            fn default() {
+                let builder = FluentBuilder::<String, Stack>::default();
+
+                let result = builder.into_value(|| "default".to_owned());
+
+                assert_eq!("default", result);
+            }
+
+            #[test]
+            fn default_value() {
+                let builder = FluentBuilder::<String, Stack>::default().value("value".to_owned());
+
+                let result = builder.into_value(|| "default".to_owned());
+
+                assert_eq!("value", result);
+            }
+
+            #[test]
+            fn default_fluent() {
+                let builder = FluentBuilder::<String, Stack>::default()
+                    .fluent_mut(|v| v.push_str("_f1"))
+                    .fluent_mut(|v| v.push_str("_f2"));
+
+                let result = builder.into_value(|| "default".to_owned());
+
+                assert_eq!("default_f1_f2", result);
+            }
+
+            #[test]
+            fn default_value_fluent() {
+                let builder = FluentBuilder::<String, Stack>::default()
+                    .value("value".to_owned())
+                    .fluent_mut(|v| v.push_str("_f1"))
+                    .fluent_mut(|v| v.push_str("_f2"));
+
+                let result = builder.into_value(|| "default".to_owned());
+
+                assert_eq!("value_f1_f2", result);
+            }
+
+            #[test]
+            fn default_fluent_value() {
+                let builder = FluentBuilder::<String, Stack>::default()
+                    .fluent_mut(|v| v.push_str("_f1"))
+                    .fluent_mut(|v| v.push_str("_f2"))
+                    .value("value".to_owned());
+
+                let result = builder.into_value(|| "default".to_owned());
+
+                assert_eq!("value", result);
+            }
+
+            #[test]
+            fn default_fluent_into_fn() {
+                let f = FluentBuilder::<String, Stack>::default()
+                    .fluent_mut(|v| v.push_str("_f1"))
+                    .fluent_mut(|v| v.push_str("_f2"))
+                    .into_fn();
+
+                let result = f("default".to_owned());
+
+                assert_eq!("default_f1_f2", result);
+            }
+
+            #[test]
+            fn value_fluent_into_fn() {
+                let f = FluentBuilder::<String, Stack>::default()
+                    .value("value".to_owned())
+                    .fluent_mut(|v| v.push_str("_f1"))
+                    .into_fn();
+
+                let result = f("default".to_owned());
+
+                assert_eq!("value_f1", result);
+            }
+
+            #[test]
+            fn from_fn_fluent() {
+                let builder = FluentBuilder::<String, Stack>::from_fn(|v| format!("{}_f1", v))
+                    .fluent_mut(|v| v.push_str("_f2"));
+
+                let result = builder.into_value(|| "default".to_owned());
+
+                assert_eq!("default_f1_f2", result);
+            }
+
+            #[test]
+            fn default_fluent_into_default_value() {
+                let builder = FluentBuilder::<String, Stack>::default()
+                    .fluent_mut(|v| v.push_str("_f1"))
+                    .fluent_mut(|v| v.push_str("_f2"));
+
+                let result = builder.into_default_value();
+
+                assert_eq!("_f1_f2", result);
+            }
+
+            #[test]
+            fn default_fluent_ref() {
+                let builder = FluentBuilder::<String, Stack>::default()
+                    .fluent_ref(|v| {
+                        v.push_str("_f1");
+                        v
+                    })
+                    .fluent_mut(|v| v.push_str("_f2"));
+
+                let result = builder.into_value(|| "default".to_owned());
+
+                assert_eq!("default_f1_f2", result);
+            }
+
+            #[test]
+            fn default_apply_to() {
+                let mut target = "target".to_owned();
 
-                assert_eq!("value", result);
+                FluentBuilder::<String, Stack>::default().apply_to(&mut target);
+
+                assert_eq!("target", target);
             }
 
             #[test]
-            fn default_fluent() {
-                let builder = FluentBuilder::<String, Stack>::default()
-                    .fluent_mut(|v| v.push_str("_f1"))
-                    .fluent_mut(|v| v.push_str("_f2"))
-                    .shared();
+            fn value_fluent_apply_to() {
+                let mut target = "target".to_owned();
 
-                let result = builder.into_value(|| "default".to_owned());
+                FluentBuilder::<String, Stack>::default()
+                    .value("value".to_owned())
+                    .fluent(|v| format!("{}_f1", v))
+                    .fluent_mut(|v| v.push_str("_f2"))
+                    .apply_to(&mut target);
 
-                assert_eq!("default_f1_f2", result);
+                assert_eq!("value_f1_f2", target);
             }
-        }
-    }
-
-    mod stateless {
-        mod fluent_override {
-            use imp::*;
 
             #[test]
-            fn default() {
-                let builder = FluentBuilder::<String>::default();
+            fn value_fluent_apply_to_panics() {
+                use std::panic::{self, AssertUnwindSafe};
 
-                let result = builder.into_value(|| "default".to_owned());
+                let mut target = "target".to_owned();
 
-                assert_eq!("default", result);
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    FluentBuilder::<String, Stack>::default()
+                        .value("value".to_owned())
+                        .fluent_mut(|_| panic!("failed at f1"))
+                        .apply_to(&mut target)
+                }));
+
+                assert!(result.is_err());
+                assert_eq!("target", target);
             }
 
             #[test]
-            fn default_value() {
-                let builder = FluentBuilder::<String>::default().value("value".to_owned());
+            fn default_fluent_apply_to_or_else_panics() {
+                use std::panic::{self, AssertUnwindSafe};
 
-                let result = builder.into_value(|| "default".to_owned());
+                let mut target = "target".to_owned();
 
-                assert_eq!("value", result);
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    FluentBuilder::<String, Stack>::default()
+                        .fluent_mut(|v| v.push_str("_f1"))
+                        .fluent_mut(|_| panic!("failed at f2"))
+                        .apply_to_or_else(&mut target, String::new)
+                }));
+
+                assert!(result.is_err());
+                assert_eq!("", target);
+
+                target.push_str("_after");
+                assert_eq!("_after", target);
             }
 
             #[test]
-            fn default_fluent() {
-                let builder = FluentBuilder::<String>::default()
-                    .fluent_mut(|v| v.push_str("_f1"))
-                    .fluent_mut(|v| v.push_str("_f2"));
+            fn default_fluent_apply_to_no_default() {
+                struct NoDefault(String);
 
-                let result = builder.into_value(|| "default".to_owned());
+                let mut target = NoDefault("target".to_owned());
 
-                assert_eq!("default_f2", result);
+                FluentBuilder::<NoDefault, Stack>::default()
+                    .fluent_mut(|v| v.0.push_str("_f1"))
+                    .apply_to(&mut target);
+
+                assert_eq!("target_f1", target.0);
             }
 
             #[test]
-            fn default_value_fluent() {
-                let builder = FluentBuilder::<String>::default()
-                    .value("value".to_owned())
-                    .fluent_mut(|v| v.push_str("_f1"))
-                    .fluent_mut(|v| v.push_str("_f2"));
+            fn default_fluent_apply_to_or_else_no_default() {
+                struct NoDefault(String);
 
-                let result = builder.into_value(|| "default".to_owned());
+                let mut target = NoDefault("target".to_owned());
 
-                assert_eq!("default_f2", result);
+                FluentBuilder::<NoDefault, Stack>::default()
+                    .fluent_mut(|v| v.0.push_str("_f1"))
+                    .apply_to_or_else(&mut target, || NoDefault("fallback".to_owned()));
+
+                assert_eq!("target_f1", target.0);
             }
 
             #[test]
-            fn value_take() {
-                let builder = FluentBuilder::<String>::default().value("value".to_owned());
+            fn value_fluent_take() {
+                let builder = FluentBuilder::<String, Stack>::default()
+                    .value("value".to_owned())
+                    .fluent_mut(|v| v.push_str("_f1"))
+                    .fluent_mut(|v| v.push_str("_f2"));
 
                 let result = match builder.try_into_value() {
                     TryIntoValue::Value(value) => value,
                     _ => panic!("expected `TryIntoValue::Value`"),
                 };
 
-                assert_eq!("value", result);
+                assert_eq!("value_f1_f2", result);
             }
 
             #[test]
-            fn default_fluent_value() {
-                let builder = FluentBuilder::<String>::default()
+            fn default_fluent_label_steps() {
+                struct CollectSteps(Vec<Step>);
+
+                impl Steps<String> for CollectSteps {
+                    fn step(
+                        &mut self,
+                        step: Step,
+                        value: String,
+                        apply: &mut dyn FnMut(String, &mut dyn Steps<String>) -> String,
+                    ) -> String {
+                        self.0.push(step);
+                        apply(value, self)
+                    }
+                }
+
+                let builder = FluentBuilder::<String, Stack>::default()
                     .fluent_mut(|v| v.push_str("_f1"))
-                    .fluent_mut(|v| v.push_str("_f2"))
-                    .value("value".to_owned());
+                    .label("push f1")
+                    .boxed()
+                    .fluent_mut(|v| v.push_str("_f2"));
 
-                let result = builder.into_value(|| "default".to_owned());
+                let mut steps = CollectSteps(Vec::new());
+                let mut fluent_method = builder
+                    .inner
+                    .into_inner()
+                    .fluent_method
+                    .expect("missing method");
 
-                assert_eq!("value", result);
-            }
-        }
+                let result = fluent_method.apply_steps(&(), "default".to_owned(), &mut steps);
 
-        mod fluent_stack {
-            use imp::*;
+                assert_eq!("default_f1_f2", result);
+
+                let steps = steps.0;
+                assert_eq!(2, steps.len());
+                assert_eq!(Some("push f1"), steps[0].label());
+                assert_eq!(None, steps[1].label());
+                assert_eq!(
+                    Some(file!()),
+                    steps[1].location().map(|location| location.file())
+                );
+            }
 
             #[test]
-            fn default() {
-                let builder = FluentBuilder::<String, Stack>::default();
+            fn default_fluent_around() {
+                let builder = FluentBuilder::<String, Stack>::default()
+                    .fluent_mut(|v| v.push_str("_f1"))
+                    .fluent_around(|v, next| {
+                        let mut v = next(format!("{}_before", v));
+                        v.push_str("_after");
+                        v
+                    })
+                    .boxed()
+                    .fluent_mut(|v| v.push_str("_f2"))
+                    .fluent(|v| format!("{}_f3", v));
 
                 let result = builder.into_value(|| "default".to_owned());
 
-                assert_eq!("default", result);
+                assert_eq!("default_f1_before_f2_f3_after", result);
             }
 
             #[test]
-            fn default_value() {
-                let builder = FluentBuilder::<String, Stack>::default().value("value".to_owned());
+            fn value_fluent_around_nested() {
+                let builder = FluentBuilder::<String, Stack>::default()
+                    .value("value".to_owned())
+                    .fluent_around(|v, next| format!("({})", next(v)))
+                    .fluent_around(|v, next| format!("[{}]", next(v)))
+                    .fluent_mut(|v| v.push_str("_f1"));
 
                 let result = builder.into_value(|| "default".to_owned());
 
-                assert_eq!("value", result);
+                assert_eq!("([value_f1])", result);
             }
 
             #[test]
-            fn default_fluent() {
+            fn default_fluent_around_skip_next() {
                 let builder = FluentBuilder::<String, Stack>::default()
-                    .fluent_mut(|v| v.push_str("_f1"))
-                    .fluent_mut(|v| v.push_str("_f2"));
+                    .fluent_around(|v, _| v)
+                    .fluent_mut(|_| unreachable!());
 
                 let result = builder.into_value(|| "default".to_owned());
 
-                assert_eq!("default_f1_f2", result);
+                assert_eq!("default", result);
             }
 
             #[test]
-            fn default_value_fluent() {
+            #[should_panic(expected = "attempted to re-use builder")]
+            fn default_fluent_around_next_twice() {
                 let builder = FluentBuilder::<String, Stack>::default()
-                    .value("value".to_owned())
-                    .fluent_mut(|v| v.push_str("_f1"))
-                    .fluent_mut(|v| v.push_str("_f2"));
-
-                let result = builder.into_value(|| "default".to_owned());
+                    .fluent_around(|v, next| {
+                        let v = next(v);
+                        next(v)
+                    })
+                    .fluent_mut(|v| v.push_str("_f1"));
 
-                assert_eq!("value_f1_f2", result);
+                let _ = builder.into_value(|| "default".to_owned());
             }
 
             #[test]
-            fn default_fluent_value() {
+            fn default_fluent_has_around() {
                 let builder = FluentBuilder::<String, Stack>::default()
                     .fluent_mut(|v| v.push_str("_f1"))
-                    .fluent_mut(|v| v.push_str("_f2"))
-                    .value("value".to_owned());
+                    .fluent(|v| format!("{}_f2", v));
+
+                let fluent_method = builder.inner.as_inner().fluent_method.as_ref();
+                assert!(!Method::<String>::has_around(
+                    fluent_method.expect("missing method")
+                ));
+
+                let builder = builder
+                    .fluent_around(|v, next| next(v))
+                    .boxed()
+                    .fluent_mut(|v| v.push_str("_f3"));
+
+                let fluent_method = builder.inner.as_inner().fluent_method.as_ref();
+                assert!(Method::<String>::has_around(
+                    fluent_method.expect("missing method")
+                ));
+            }
+        }
 
-                let result = builder.into_value(|| "default".to_owned());
+        mod fluent_custom {
+            use imp::*;
 
-                assert_eq!("value", result);
+            // Keeps the value, but overrides fluent methods
+            enum KeepValue {}
+
+            impl StackMode for KeepValue {
+                type Keep = Override;
+
+                fn stack<TValue, TMethod>(
+                    value: Option<TValue>,
+                    _: Option<TMethod>,
+                    _: NextMethod<TValue>,
+                ) -> (Option<TValue>, Option<TMethod>) {
+                    (value, None)
+                }
+            }
+
+            fn push<TStack: StackMode>(
+                builder: BoxedFluentBuilder<String, TStack>,
+                suffix: &'static str,
+            ) -> BoxedFluentBuilder<String, TStack> {
+                builder.fluent_mut(move |v| v.push_str(suffix)).boxed()
             }
 
             #[test]
-            fn value_fluent_take() {
-                let builder = FluentBuilder::<String, Stack>::default()
+            fn default_value_fluent() {
+                let builder = FluentBuilder::<String, KeepValue>::default()
                     .value("value".to_owned())
-                    .fluent_mut(|v| v.push_str("_f1"))
-                    .fluent_mut(|v| v.push_str("_f2"));
+                    .boxed();
 
-                let result = match builder.try_into_value() {
-                    TryIntoValue::Value(value) => value,
-                    _ => panic!("expected `TryIntoValue::Value`"),
-                };
+                let builder = push(push(builder, "_f1"), "_f2");
 
-                assert_eq!("value_f1_f2", result);
+                let result = builder.into_value(|| "default".to_owned());
+
+                assert_eq!("value_f2", result);
+            }
+
+            #[test]
+            fn generic_fluent() {
+                let stack = push(
+                    push(BoxedFluentBuilder::<String, Stack>::default(), "_f1"),
+                    "_f2",
+                );
+                let over = push(
+                    push(BoxedFluentBuilder::<String, Override>::default(), "_f1"),
+                    "_f2",
+                );
+
+                assert_eq!("default_f1_f2", stack.into_value(|| "default".to_owned()));
+                assert_eq!("default_f2", over.into_value(|| "default".to_owned()));
             }
         }
     }
@@ -1416,12 +4215,73 @@ mod tests {
 
                 assert_eq!(expected, result);
             }
+
+            #[test]
+            fn from_seed_fluent_seed_with_seed() {
+                let builder =
+                    StatefulFluentBuilder::<String, Builder>::from_seed("seed".to_owned())
+                        .fluent_mut("f1".to_owned(), |v| v.optional = Some("f1".to_owned()))
+                        .fluent_seed(|seed| format!("{}_s1", seed))
+                        .fluent_mut_with_seed(|v| v.optional = Some("f2".to_owned()));
+
+                assert_eq!(Some(&"f1_s1".to_owned()), builder.seed());
+
+                let result = builder.into_value(|seed| Builder {
+                    required: seed,
+                    optional: None,
+                });
+
+                let expected = Builder {
+                    required: "f1_s1".to_owned(),
+                    optional: Some("f2".to_owned()),
+                };
+
+                assert_eq!(expected, result);
+            }
+
+            #[test]
+            fn build_many_fluent() {
+                let append = |v| format!("{}_f2", v);
+
+                let builder = StatefulFluentBuilder::<&str, String>::from_seed("seed")
+                    .fluent_mut("_f1", |v| v.push_str("_f1"))
+                    .fluent("_f2", append);
+
+                let results: Vec<_> = builder
+                    .build_many(vec!["seed1", "seed2"], |seed| seed.to_owned())
+                    .collect();
+
+                assert_eq!(vec!["seed1_f2", "seed2_f2"], results);
+            }
+
+            #[test]
+            fn from_value_with_seed() {
+                let builder = StatefulFluentBuilder::<String, Builder>::from_value(Builder {
+                    required: "value".to_owned(),
+                    optional: None,
+                })
+                .fluent_mut_with_seed(|v| v.optional = Some("f1".to_owned()));
+
+                assert_eq!(None, builder.seed());
+
+                let result = builder.into_value(|seed| Builder {
+                    required: seed,
+                    optional: None,
+                });
+
+                let expected = Builder {
+                    required: "value".to_owned(),
+                    optional: Some("f1".to_owned()),
+                };
+
+                assert_eq!(expected, result);
+            }
         }
 
         mod fluent_stack {
             use imp::*;
 
-            #[derive(Debug, PartialEq, Eq)]
+            #[derive(Debug, Default, PartialEq, Eq)]
             struct Builder {
                 required: String,
                 optional: Option<String>,
@@ -1601,6 +4461,177 @@ mod tests {
 
                 assert_eq!(expected, result);
             }
+
+            #[test]
+            fn from_option_value() {
+                let builder =
+                    StatefulFluentBuilder::<String, Builder, Stack>::from(Some(Builder {
+                        required: "value".to_owned(),
+                        optional: None,
+                    }));
+
+                let result = builder.into_value(|seed| Builder {
+                    required: seed,
+                    optional: None,
+                });
+
+                let expected = Builder {
+                    required: "value".to_owned(),
+                    optional: None,
+                };
+
+                assert_eq!(expected, result);
+            }
+
+            #[test]
+            fn from_option_none() {
+                let builder = StatefulFluentBuilder::<String, Builder, Stack>::from(None);
+
+                let result = builder.into_value(|seed| Builder {
+                    required: seed,
+                    optional: None,
+                });
+
+                let expected = Builder {
+                    required: "".to_owned(),
+                    optional: None,
+                };
+
+                assert_eq!(expected, result);
+            }
+
+            #[test]
+            fn default_fluent_into_value_from_seed() {
+                let builder = StatefulFluentBuilder::<&str, String, Stack>::default()
+                    .fluent_mut("f1", |s, v| v.push_str(s))
+                    .fluent_mut("f2", |s, v| v.push_str(s));
+
+                let result = builder.into_value_from_seed();
+
+                assert_eq!("f1f2", result);
+            }
+
+            #[test]
+            fn from_seed_fluent_apply_to() {
+                let mut target = Builder {
+                    required: "target".to_owned(),
+                    optional: Some("target".to_owned()),
+                };
+
+                StatefulFluentBuilder::<String, Builder, Stack>::from_seed("seed".to_owned())
+                    .fluent_mut("f1".to_owned(), |s, v| v.required = s)
+                    .fluent_mut("f2".to_owned(), |_, v| {
+                        if let Some(ref mut optional) = v.optional.as_mut() {
+                            optional.push_str("_f2");
+                        }
+                    })
+                    .apply_to(&mut target);
+
+                let expected = Builder {
+                    required: "f1".to_owned(),
+                    optional: Some("target_f2".to_owned()),
+                };
+
+                assert_eq!(expected, target);
+            }
+
+            #[test]
+            fn from_seed_fluent_with_seed() {
+                let builder = StatefulFluentBuilder::<&str, String, Stack>::from_seed("seed")
+                    .fluent_mut_with_seed(|s, v| v.push_str(s))
+                    .fluent_seed(|_| "s1")
+                    .fluent_with_seed(|s, v| format!("{}_{}", v, s));
+
+                let result = builder.into_value(|seed| format!("{}_", seed));
+
+                assert_eq!("s1_seed_s1", result);
+            }
+
+            #[test]
+            fn from_value_fluent_seed() {
+                let builder =
+                    StatefulFluentBuilder::<&str, String, Stack>::from_value("value".to_owned())
+                        .fluent_seed(|_| panic!("the seed shouldn't be transformed"))
+                        .fluent_mut("f1", |s, v| v.push_str(s));
+
+                assert_eq!(None, builder.seed());
+
+                let result = builder.into_value(|seed| seed.to_owned());
+
+                assert_eq!("valuef1", result);
+            }
+
+            #[test]
+            fn from_value_fluent_with_seed() {
+                let builder =
+                    StatefulFluentBuilder::<&str, String, Stack>::from_value("value".to_owned())
+                        .fluent_mut("_f1", |s, v| v.push_str(s))
+                        .fluent_mut_with_seed(|_, _| {
+                            panic!("there's no seed to apply the method with")
+                        })
+                        .fluent_mut("_f2", |s, v| v.push_str(s));
+
+                let result = builder.into_value(|seed| seed.to_owned());
+
+                assert_eq!("value_f1_f2", result);
+            }
+
+            fn push_seed(seed: &str, value: &mut String) {
+                value.push_str(seed);
+            }
+
+            #[test]
+            fn build_many_fluent() {
+                let append = |s, v| format!("{}{}", v, s);
+
+                let builder = StatefulFluentBuilder::<&str, String, Stack>::from_seed("seed")
+                    .fluent_mut("_f1", push_seed)
+                    .fluent("_f2", append);
+
+                let results: Vec<_> = builder
+                    .build_many(vec!["seed1", "seed2"], |seed| seed.to_owned())
+                    .collect();
+
+                assert_eq!(vec!["seed1_f1_f2", "seed2_f1_f2"], results);
+            }
+
+            #[test]
+            fn from_value_build_many_fluent() {
+                let append = |s, v| format!("{}{}", v, s);
+
+                let builder =
+                    StatefulFluentBuilder::<&str, String, Stack>::from_value("value".to_owned())
+                        .fluent("_f1", append);
+
+                let results: Vec<_> = builder
+                    .build_many(vec!["seed1", "seed2"], |_| {
+                        panic!("the value should be cloned")
+                    })
+                    .collect();
+
+                assert_eq!(vec!["value_f1", "value_f1"], results);
+            }
+
+            #[test]
+            fn build_many_shares_fluent() {
+                use std::cell::Cell;
+
+                let applied = Cell::new(0);
+                let push = |s, v: &mut String| {
+                    applied.set(applied.get() + 1);
+                    v.push_str(s);
+                };
+
+                let builder = StatefulFluentBuilder::<&str, String, Stack>::from_seed("seed")
+                    .fluent_mut("_f1", push);
+
+                let results: Vec<_> = builder
+                    .build_many(vec!["seed1", "seed2", "seed3"], |seed| seed.to_owned())
+                    .collect();
+
+                assert_eq!(vec!["seed1_f1", "seed2_f1", "seed3_f1"], results);
+                assert_eq!(3, applied.get());
+            }
         }
     }
 }
@@ -0,0 +1,168 @@
+/*!
+Values that know how to construct and finish their own builders.
+*/
+
+use super::{FluentBuilder, Storage};
+
+/**
+A value that's constructed from a builder.
+
+Implementing `Buildable` for a value lets a `FluentBuilder` of its builder be finished using `build`,
+without having to supply the default builder at each call site.
+That means trees of nested builders can be finished recursively.
+
+# Examples
+
+```
+use fluent_builder::{Buildable, FluentBuilder, Stack};
+
+struct Body(Vec<u8>);
+
+#[derive(Default)]
+struct BodyBuilder {
+    bytes: Vec<u8>,
+}
+
+impl Buildable for Body {
+    type Builder = BodyBuilder;
+
+    fn default_builder() -> BodyBuilder {
+        BodyBuilder::default()
+    }
+
+    fn finish(builder: BodyBuilder) -> Body {
+        Body(builder.bytes)
+    }
+}
+
+let body: Body = FluentBuilder::<BodyBuilder, Stack>::default()
+    .fluent_mut(|b| b.bytes.extend(b"some"))
+    .fluent_mut(|b| b.bytes.extend(b" bytes"))
+    .build();
+
+assert_eq!(b"some bytes", &*body.0);
+```
+*/
+pub trait Buildable: Sized {
+    /**
+    The builder for the value.
+    */
+    type Builder;
+
+    /**
+    Create the builder to use when a fluent builder doesn't contain one.
+    */
+    fn default_builder() -> Self::Builder;
+
+    /**
+    Finish the builder to produce a value.
+    */
+    fn finish(builder: Self::Builder) -> Self;
+}
+
+impl<TValue, TStack, TStorage> FluentBuilder<TValue, TStack, TStorage>
+where
+    TStorage: Storage<TValue>,
+{
+    /**
+    Convert the fluent builder into a builder for `TBuilt`, and then finish it.
+
+    This method behaves the same as `into_value(TBuilt::default_builder)`,
+    followed by `TBuilt::finish`.
+    */
+    pub fn build<TBuilt>(self) -> TBuilt
+    where
+        TBuilt: Buildable<Builder = TValue>,
+    {
+        TBuilt::finish(self.into_value(TBuilt::default_builder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use imp::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Request {
+        host: String,
+        body: Body,
+    }
+
+    #[derive(Default)]
+    struct RequestBuilder {
+        host: Option<String>,
+        body: BoxedFluentBuilder<BodyBuilder, Stack>,
+    }
+
+    impl Buildable for Request {
+        type Builder = RequestBuilder;
+
+        fn default_builder() -> RequestBuilder {
+            RequestBuilder::default()
+        }
+
+        fn finish(builder: RequestBuilder) -> Request {
+            Request {
+                host: builder.host.unwrap_or_else(|| "localhost".to_owned()),
+                body: builder.body.build(),
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Body(String);
+
+    #[derive(Default)]
+    struct BodyBuilder(String);
+
+    impl Buildable for Body {
+        type Builder = BodyBuilder;
+
+        fn default_builder() -> BodyBuilder {
+            BodyBuilder("default".to_owned())
+        }
+
+        fn finish(builder: BodyBuilder) -> Body {
+            Body(builder.0)
+        }
+    }
+
+    #[test]
+    fn default_build() {
+        let request: Request = FluentBuilder::<RequestBuilder, Stack>::default().build();
+
+        let expected = Request {
+            host: "localhost".to_owned(),
+            body: Body("default".to_owned()),
+        };
+
+        assert_eq!(expected, request);
+    }
+
+    #[test]
+    fn nested_fluent_build() {
+        let request: Request = FluentBuilder::<RequestBuilder, Stack>::default()
+            .fluent_mut(|r| r.host = Some("example.com".to_owned()))
+            .fluent(|mut r| {
+                r.body = r.body.fluent_mut(|b| b.0.push_str("_f1")).boxed();
+                r
+            })
+            .build();
+
+        let expected = Request {
+            host: "example.com".to_owned(),
+            body: Body("default_f1".to_owned()),
+        };
+
+        assert_eq!(expected, request);
+    }
+
+    #[test]
+    fn value_build() {
+        let body: Body = FluentBuilder::<BodyBuilder>::default()
+            .value(BodyBuilder("value".to_owned()))
+            .build();
+
+        assert_eq!(Body("value".to_owned()), body);
+    }
+}
@@ -0,0 +1,363 @@
+/*!
+Builders that fluent methods can be added to from multiple threads at once.
+*/
+
+use std::fmt;
+use std::panic::Location;
+use std::sync::{Mutex, PoisonError};
+
+use super::{
+    Apply, ByRefMut, ByValue, FluentBuilder, Inline, Method, SharedFluentBuilder, SharedMethod,
+    Stack, State, StatefulFluentBuilder, StatefulFluentBuilderInner,
+};
+
+/**
+A stacking builder that fluent methods can be added to through a shared reference.
+
+Methods added using `fluent` are applied in the order they're registered when the builder is finished.
+For an order that doesn't depend on how threads are scheduled, methods can be given an explicit sequence number using `fluent_at`.
+Methods with an explicit number are applied before any methods added using `fluent`, in ascending order of that number.
+Methods with the same number are applied in the order they were registered.
+
+# Examples
+
+```
+use std::thread;
+use fluent_builder::ConcurrentFluentBuilder;
+
+let builder = ConcurrentFluentBuilder::<Vec<usize>>::new();
+
+thread::scope(|scope| {
+    for plugin in 0..4 {
+        let builder = &builder;
+        scope.spawn(move || builder.fluent_mut_at(plugin as u64, move |v| v.push(plugin)));
+    }
+});
+
+assert_eq!(vec![0, 1, 2, 3], builder.finish(Vec::new));
+```
+*/
+#[must_use = "builders do nothing unless they're converted into a value"]
+pub struct ConcurrentFluentBuilder<TValue> {
+    registered: Mutex<Registered<TValue>>,
+}
+
+struct Registered<TValue> {
+    next_sequence: u64,
+    fluent_methods: Vec<(Order, Box<dyn Method<TValue> + Send>)>,
+}
+
+/**
+The position of a fluent method in the sequence.
+
+Explicit numbers are ordered before automatic ones, so the two never interleave.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Order {
+    Explicit(u64),
+    Auto(u64),
+}
+
+impl<TValue> Default for ConcurrentFluentBuilder<TValue> {
+    fn default() -> Self {
+        ConcurrentFluentBuilder {
+            registered: Mutex::new(Registered {
+                next_sequence: 0,
+                fluent_methods: Vec::new(),
+            }),
+        }
+    }
+}
+
+impl<TValue> ConcurrentFluentBuilder<TValue>
+where
+    TValue: Send + 'static,
+{
+    /**
+    Create a builder without any fluent methods.
+    */
+    pub fn new() -> Self {
+        ConcurrentFluentBuilder::default()
+    }
+
+    /**
+    Stack a fluent method on the builder, returning the sequence number it was given.
+
+    The method will be applied after any methods registered before it, and after any methods added using `fluent_at`.
+    */
+    #[track_caller]
+    pub fn fluent<TNextMethod>(&self, fluent_method: TNextMethod) -> u64
+    where
+        TNextMethod: FnOnce(TValue) -> TValue + Send + 'static,
+    {
+        let fluent_method =
+            Apply::<TValue, Inline, _>::new(None, ByValue(fluent_method), Location::caller());
+
+        self.push(None, Box::new(fluent_method))
+    }
+
+    /**
+    Stack a fluent method on the builder, returning the sequence number it was given.
+
+    This method behaves the same as `fluent`, but mutates the value instead of replacing it.
+    */
+    #[track_caller]
+    pub fn fluent_mut<TNextMethod>(&self, fluent_method: TNextMethod) -> u64
+    where
+        TNextMethod: FnOnce(&mut TValue) + Send + 'static,
+    {
+        let fluent_method =
+            Apply::<TValue, Inline, _>::new(None, ByRefMut(fluent_method), Location::caller());
+
+        self.push(None, Box::new(fluent_method))
+    }
+
+    /**
+    Stack a fluent method on the builder with the given sequence number.
+
+    The method will be applied before any methods added using `fluent`, and after any methods with a lower sequence number.
+    Methods with the same number are applied in the order they were registered.
+    */
+    #[track_caller]
+    pub fn fluent_at<TNextMethod>(&self, sequence: u64, fluent_method: TNextMethod)
+    where
+        TNextMethod: FnOnce(TValue) -> TValue + Send + 'static,
+    {
+        let fluent_method =
+            Apply::<TValue, Inline, _>::new(None, ByValue(fluent_method), Location::caller());
+
+        self.push(Some(sequence), Box::new(fluent_method));
+    }
+
+    /**
+    Stack a fluent method on the builder with the given sequence number.
+
+    This method behaves the same as `fluent_at`, but mutates the value instead of replacing it.
+    */
+    #[track_caller]
+    pub fn fluent_mut_at<TNextMethod>(&self, sequence: u64, fluent_method: TNextMethod)
+    where
+        TNextMethod: FnOnce(&mut TValue) + Send + 'static,
+    {
+        let fluent_method =
+            Apply::<TValue, Inline, _>::new(None, ByRefMut(fluent_method), Location::caller());
+
+        self.push(Some(sequence), Box::new(fluent_method));
+    }
+
+    /**
+    The number of fluent methods that have been registered.
+    */
+    pub fn len(&self) -> usize {
+        self.registered
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .fluent_methods
+            .len()
+    }
+
+    /**
+    Whether no fluent methods have been registered.
+    */
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /**
+    Convert the builder into a stacking shared builder, with its fluent methods in sequence order.
+    */
+    pub fn into_builder(self) -> SharedFluentBuilder<TValue, Stack> {
+        let mut fluent_methods = self
+            .registered
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+            .fluent_methods;
+
+        // The sort is stable, so methods with the same sequence number keep the order they were registered in
+        fluent_methods.sort_by_key(|&(order, _)| order);
+
+        let fluent_method = if fluent_methods.is_empty() {
+            None
+        } else {
+            let fluent_methods = fluent_methods.into_iter().map(|(_, f)| f).collect();
+
+            Some(SharedMethod(fluent_methods))
+        };
+
+        FluentBuilder {
+            inner: StatefulFluentBuilder::new(StatefulFluentBuilderInner {
+                state: State::Seed(()),
+                fluent_method,
+            }),
+        }
+    }
+
+    /**
+    Build the value once all fluent methods have been registered.
+
+    The fluent methods are applied over the default value in sequence order.
+    Taking the builder by-value means no more methods can be registered while it's being finished.
+    */
+    pub fn finish<TDefault>(self, default_value: TDefault) -> TValue
+    where
+        TDefault: FnOnce() -> TValue,
+    {
+        self.into_builder().into_value(default_value)
+    }
+
+    fn push(&self, sequence: Option<u64>, fluent_method: Box<dyn Method<TValue> + Send>) -> u64 {
+        let mut registered = self
+            .registered
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        // Numbers are given out while the lock is held, so they match the order methods are registered in
+        let (order, sequence) = match sequence {
+            Some(sequence) => (Order::Explicit(sequence), sequence),
+            None => {
+                let sequence = registered.next_sequence;
+                registered.next_sequence += 1;
+
+                (Order::Auto(sequence), sequence)
+            }
+        };
+
+        registered.fluent_methods.push((order, fluent_method));
+
+        sequence
+    }
+}
+
+impl<TValue> fmt::Debug for ConcurrentFluentBuilder<TValue> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let registered = self
+            .registered
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        f.debug_struct("ConcurrentFluentBuilder")
+            .field(
+                "sequence",
+                &registered
+                    .fluent_methods
+                    .iter()
+                    .map(|&(order, _)| order)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+
+    use imp::*;
+
+    #[test]
+    fn default_fluent_sequence() {
+        let builder = ConcurrentFluentBuilder::<String>::new();
+
+        assert_eq!(0, builder.fluent_mut(|v| v.push_str("_f1")));
+        assert_eq!(1, builder.fluent(|v| format!("{}_f2", v)));
+
+        builder.fluent_mut_at(0, |v| v.push_str("_f0"));
+
+        assert_eq!(3, builder.len());
+
+        let result = builder.finish(|| "default".to_owned());
+
+        assert_eq!("default_f0_f1_f2", result);
+    }
+
+    #[test]
+    fn default_fluent_at_across_threads() {
+        let builder = Arc::new(ConcurrentFluentBuilder::<String>::new());
+
+        let handles: Vec<_> = (0..8u64)
+            .rev()
+            .map(|i| {
+                let builder = builder.clone();
+                thread::spawn(move || builder.fluent_mut_at(i, move |v| v.push_str(&i.to_string())))
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let builder = Arc::try_unwrap(builder).unwrap();
+        let result = builder.finish(|| "default_".to_owned());
+
+        assert_eq!("default_01234567", result);
+    }
+
+    #[test]
+    fn default_fluent_at_sequence() {
+        let builder = ConcurrentFluentBuilder::<String>::new();
+
+        builder.fluent_mut_at(5, |v| v.push_str("_f5"));
+        builder.fluent_mut(|v| v.push_str("_auto"));
+        builder.fluent_mut_at(1, |v| v.push_str("_f1a"));
+        builder.fluent_mut_at(1, |v| v.push_str("_f1b"));
+
+        let result = builder.finish(|| "default".to_owned());
+
+        assert_eq!("default_f1a_f1b_f5_auto", result);
+    }
+
+    #[test]
+    fn default_into_builder() {
+        let builder = ConcurrentFluentBuilder::<String>::new();
+
+        assert!(builder.is_empty());
+
+        let builder = builder
+            .into_builder()
+            .fluent_mut(|v| v.push_str("_f1"))
+            .shared();
+
+        assert_eq!("default_f1", builder.into_value(|| "default".to_owned()));
+    }
+
+    #[test]
+    fn default_fluent_sequence_across_threads() {
+        let builder = Arc::new(ConcurrentFluentBuilder::<Vec<usize>>::new());
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let builder = builder.clone();
+                thread::spawn(move || (builder.fluent_mut(move |v| v.push(i)), i))
+            })
+            .collect();
+
+        let mut registered: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        registered.sort();
+
+        let builder = Arc::try_unwrap(builder).unwrap();
+        let result = builder.finish(Vec::new);
+
+        let expected: Vec<_> = registered.into_iter().map(|(_, i)| i).collect();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn default_into_builder_fluent_around() {
+        let builder = ConcurrentFluentBuilder::<String>::new();
+
+        builder.fluent_mut(|v| v.push_str("_f1"));
+        builder.fluent_mut(|v| v.push_str("_f2"));
+
+        let builder = builder
+            .into_builder()
+            .fluent_around(|v, next| format!("{}_around", next(v)))
+            .fluent_mut(|v| v.push_str("_f3"));
+
+        assert_eq!(
+            "default_f1_f2_f3_around",
+            builder.into_value(|| "default".to_owned())
+        );
+    }
+}
@@ -0,0 +1,752 @@
+/*!
+Fluent methods that receive a context supplied when the builder is converted into a value.
+
+The context is only known at build time, unlike a seed.
+Builders carry the type of their context as a parameter, which is `()` until a context-aware method is added,
+so builders with context-aware methods can only be converted into a value using `into_value_with`.
+
+# Examples
+
+```
+# use fluent_builder::{FluentBuilder, Stack};
+enum Env {
+    Dev,
+    Prod,
+}
+
+let builder = || {
+    FluentBuilder::<String, Stack>::default()
+        .fluent_mut(|s| s.push_str("api"))
+        .fluent_ctx(|env: &Env, s| match *env {
+            Env::Dev => format!("{}.dev.example.com", s),
+            Env::Prod => format!("{}.example.com", s),
+        })
+};
+
+assert_eq!("api.dev.example.com", builder().into_value_with(&Env::Dev, String::new));
+assert_eq!("api.example.com", builder().into_value_with(&Env::Prod, String::new));
+```
+
+A builder with context-aware methods can't be converted into a value without a context:
+
+```compile_fail
+# use fluent_builder::{FluentBuilder, Stack};
+let value = FluentBuilder::<String, Stack>::default()
+    .fluent_ctx(|env: &&str, s| format!("{}.{}", s, env))
+    .into_value(String::new);
+```
+*/
+
+use std::panic::Location;
+
+use super::eager::Immediate;
+use super::{
+    apply_previous, Apply, DefaultStorage, Direct, FluentBuilder, Kept, Method, Override, Stack,
+    StackMode, State, StatefulApply, StatefulFluentBuilder, Steps, Storage,
+};
+
+pub struct ByValueCtx<TFluent>(TFluent);
+
+pub struct ByRefMutCtx<TFluent>(TFluent);
+
+impl<TValue, TFluent> Immediate<TValue> for ByValueCtx<TFluent> {
+    fn apply_immediately(
+        self,
+        value: TValue,
+        _: &'static Location<'static>,
+    ) -> Result<TValue, (Self, TValue)> {
+        Err((self, value))
+    }
+}
+
+impl<TValue, TFluent> Immediate<TValue> for ByRefMutCtx<TFluent> {
+    fn apply_immediately(
+        self,
+        value: TValue,
+        _: &'static Location<'static>,
+    ) -> Result<TValue, (Self, TValue)> {
+        Err((self, value))
+    }
+}
+
+impl<TValue, TStack, TStorage, TContext: ?Sized> FluentBuilder<TValue, TStack, TStorage, TContext>
+where
+    TStack: StackMode,
+    TStorage: Storage<TValue, TContext>,
+{
+    /**
+    Add a context-aware fluent method to the builder.
+
+    This method behaves the same as `fluent`, but the method is also given the context passed to `into_value_with`.
+    Any fluent methods already on the builder are kept, and need to accept the same context.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn fluent_ctx<TNextContext, TNextMethod>(
+        self,
+        fluent_method: TNextMethod,
+    ) -> FluentBuilder<
+        TValue,
+        TStack,
+        Apply<
+            TValue,
+            Kept<TStack, TValue, TNextContext, TStorage::Method>,
+            ByValueCtx<TNextMethod>,
+        >,
+        TNextContext,
+    >
+    where
+        TNextContext: ?Sized,
+        TNextMethod: FnOnce(&TNextContext, TValue) -> TValue,
+        TStorage::Method: Method<TValue, TNextContext>,
+    {
+        self.stack(ByValueCtx(fluent_method), Location::caller())
+    }
+
+    /**
+    Add a context-aware fluent method to the builder.
+
+    This method behaves the same as `fluent_ctx`, but mutates the value instead of replacing it.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn fluent_ctx_mut<TNextContext, TNextMethod>(
+        self,
+        fluent_method: TNextMethod,
+    ) -> FluentBuilder<
+        TValue,
+        TStack,
+        Apply<
+            TValue,
+            Kept<TStack, TValue, TNextContext, TStorage::Method>,
+            ByRefMutCtx<TNextMethod>,
+        >,
+        TNextContext,
+    >
+    where
+        TNextContext: ?Sized,
+        TNextMethod: FnOnce(&TNextContext, &mut TValue),
+        TStorage::Method: Method<TValue, TNextContext>,
+    {
+        self.stack(ByRefMutCtx(fluent_method), Location::caller())
+    }
+}
+
+impl<TSeed, TValue, TStorage, TContext: ?Sized>
+    StatefulFluentBuilder<TSeed, TValue, Stack, TStorage, TContext>
+where
+    TStorage: Storage<TValue, TContext>,
+{
+    /**
+    Stack a context-aware fluent method on the builder.
+
+    This method behaves the same as `fluent`, but the method is also given the context passed to `into_value_with`.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn fluent_ctx<TNextContext, TNextMethod>(
+        self,
+        seed: TSeed,
+        fluent_method: TNextMethod,
+    ) -> StatefulFluentBuilder<
+        TSeed,
+        TValue,
+        Stack,
+        StatefulApply<TSeed, TValue, TStorage::Method, ByValueCtx<TNextMethod>>,
+        TNextContext,
+    >
+    where
+        TNextContext: ?Sized,
+        TNextMethod: FnOnce(TSeed, &TNextContext, TValue) -> TValue,
+        TStorage::Method: Method<TValue, TNextContext>,
+    {
+        let location = Location::caller();
+
+        self.stack(move |previous_fluent_method| {
+            StatefulApply::new(
+                seed,
+                previous_fluent_method,
+                ByValueCtx(fluent_method),
+                location,
+            )
+        })
+    }
+
+    /**
+    Stack a context-aware fluent method on the builder.
+
+    This method behaves the same as `fluent_ctx`, but mutates the value instead of replacing it.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn fluent_ctx_mut<TNextContext, TNextMethod>(
+        self,
+        seed: TSeed,
+        fluent_method: TNextMethod,
+    ) -> StatefulFluentBuilder<
+        TSeed,
+        TValue,
+        Stack,
+        StatefulApply<TSeed, TValue, TStorage::Method, ByRefMutCtx<TNextMethod>>,
+        TNextContext,
+    >
+    where
+        TNextContext: ?Sized,
+        TNextMethod: FnOnce(TSeed, &TNextContext, &mut TValue),
+        TStorage::Method: Method<TValue, TNextContext>,
+    {
+        let location = Location::caller();
+
+        self.stack(move |previous_fluent_method| {
+            StatefulApply::new(
+                seed,
+                previous_fluent_method,
+                ByRefMutCtx(fluent_method),
+                location,
+            )
+        })
+    }
+}
+
+impl<TSeed, TValue, TStorage, TContext: ?Sized>
+    StatefulFluentBuilder<TSeed, TValue, Override, TStorage, TContext>
+where
+    TStorage: Storage<TValue, TContext>,
+{
+    /**
+    Set the context-aware fluent method on the builder.
+
+    This method behaves the same as `fluent`, but the method is also given the context passed to `into_value_with`.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
+    pub fn fluent_ctx<TNextContext, TNextMethod>(
+        self,
+        seed: TSeed,
+        fluent_method: TNextMethod,
+    ) -> StatefulFluentBuilder<
+        TSeed,
+        TValue,
+        Override,
+        Apply<TValue, DefaultStorage, ByValueCtx<TNextMethod>>,
+        TNextContext,
+    >
+    where
+        TNextContext: ?Sized,
+        TNextMethod: FnOnce(&TNextContext, TValue) -> TValue,
+    {
+        self.discard();
+
+        Self::with_fluent_method(
+            State::Seed(seed),
+            ByValueCtx(fluent_method),
+            Location::caller(),
+        )
+    }
+
+    /**
+    Set the context-aware fluent method on the builder.
+
+    This method behaves the same as `fluent_ctx`, but mutates the value instead of replacing it.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
+    pub fn fluent_ctx_mut<TNextContext, TNextMethod>(
+        self,
+        seed: TSeed,
+        fluent_method: TNextMethod,
+    ) -> StatefulFluentBuilder<
+        TSeed,
+        TValue,
+        Override,
+        Apply<TValue, DefaultStorage, ByRefMutCtx<TNextMethod>>,
+        TNextContext,
+    >
+    where
+        TNextContext: ?Sized,
+        TNextMethod: FnOnce(&TNextContext, &mut TValue),
+    {
+        self.discard();
+
+        Self::with_fluent_method(
+            State::Seed(seed),
+            ByRefMutCtx(fluent_method),
+            Location::caller(),
+        )
+    }
+}
+
+impl<TValue, TContext: ?Sized, TPreviousMethod, TNextMethod> Method<TValue, TContext>
+    for Apply<TValue, TPreviousMethod, ByValueCtx<TNextMethod>>
+where
+    TPreviousMethod: Method<TValue, TContext>,
+    TNextMethod: FnOnce(&TContext, TValue) -> TValue,
+{
+    fn apply(&mut self, ctx: &TContext, value: TValue) -> TValue {
+        let inner = self.inner.take().expect("attempted to re-use builder");
+
+        let (next, inner) = inner.take_next();
+
+        inner
+            .set_next(ByValueCtx(move |_, ctx: &TContext, value: TValue| {
+                (next.0)(ctx, value)
+            }))
+            .apply(ctx, value)
+    }
+
+    fn apply_steps(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+    ) -> TValue {
+        let inner = self.inner.take().expect("attempted to re-use builder");
+
+        let (next, inner) = inner.take_next();
+
+        inner
+            .set_next(ByValueCtx(move |_, ctx: &TContext, value: TValue| {
+                (next.0)(ctx, value)
+            }))
+            .apply_steps(ctx, value, steps)
+    }
+
+    fn apply_then(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        let inner = self.inner.take().expect("attempted to re-use builder");
+
+        let (next, inner) = inner.take_next();
+
+        inner
+            .set_next(ByValueCtx(move |_, ctx: &TContext, value: TValue| {
+                (next.0)(ctx, value)
+            }))
+            .apply_then(ctx, value, steps, then)
+    }
+
+    fn has_around(&self) -> bool {
+        self.inner
+            .as_ref()
+            .is_some_and(|inner| inner.previous_has_around::<TContext>())
+    }
+
+    fn set_label(&mut self, label: &'static str) {
+        Apply::set_label(self, label)
+    }
+}
+
+impl<TValue, TContext: ?Sized, TPreviousMethod, TNextMethod> Method<TValue, TContext>
+    for Apply<TValue, TPreviousMethod, ByRefMutCtx<TNextMethod>>
+where
+    TPreviousMethod: Method<TValue, TContext>,
+    TNextMethod: FnOnce(&TContext, &mut TValue),
+{
+    fn apply(&mut self, ctx: &TContext, value: TValue) -> TValue {
+        let inner = self.inner.take().expect("attempted to re-use builder");
+
+        let (next, inner) = inner.take_next();
+
+        inner
+            .set_next(ByRefMutCtx(move |_, ctx: &TContext, value: &mut TValue| {
+                (next.0)(ctx, value)
+            }))
+            .apply(ctx, value)
+    }
+
+    fn apply_steps(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+    ) -> TValue {
+        let inner = self.inner.take().expect("attempted to re-use builder");
+
+        let (next, inner) = inner.take_next();
+
+        inner
+            .set_next(ByRefMutCtx(move |_, ctx: &TContext, value: &mut TValue| {
+                (next.0)(ctx, value)
+            }))
+            .apply_steps(ctx, value, steps)
+    }
+
+    fn apply_then(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        let inner = self.inner.take().expect("attempted to re-use builder");
+
+        let (next, inner) = inner.take_next();
+
+        inner
+            .set_next(ByRefMutCtx(move |_, ctx: &TContext, value: &mut TValue| {
+                (next.0)(ctx, value)
+            }))
+            .apply_then(ctx, value, steps, then)
+    }
+
+    fn has_around(&self) -> bool {
+        self.inner
+            .as_ref()
+            .is_some_and(|inner| inner.previous_has_around::<TContext>())
+    }
+
+    fn set_label(&mut self, label: &'static str) {
+        Apply::set_label(self, label)
+    }
+}
+
+impl<TSeed, TValue, TContext: ?Sized, TPreviousMethod, TNextMethod> Method<TValue, TContext>
+    for StatefulApply<TSeed, TValue, TPreviousMethod, ByValueCtx<TNextMethod>>
+where
+    TPreviousMethod: Method<TValue, TContext>,
+    TNextMethod: FnOnce(TSeed, &TContext, TValue) -> TValue,
+{
+    fn apply(&mut self, ctx: &TContext, value: TValue) -> TValue {
+        if self.has_around() {
+            return self.apply_then(ctx, value, &mut Direct, &mut |value, _| value);
+        }
+
+        let seed = self.seed.take().expect("attempted to re-use builder");
+        let next = self.next.take().expect("attempted to re-use builder");
+
+        let value = match self.previous {
+            Some(ref mut previous) => previous.apply(ctx, value),
+            None => value,
+        };
+
+        (next.0)(seed, ctx, value)
+    }
+
+    fn apply_steps(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+    ) -> TValue {
+        if self.has_around() {
+            return self.apply_then(ctx, value, steps, &mut |value, _| value);
+        }
+
+        let seed = self.seed.take().expect("attempted to re-use builder");
+        let next = self.next.take().expect("attempted to re-use builder");
+
+        let value = match self.previous {
+            Some(ref mut previous) => previous.apply_steps(ctx, value, steps),
+            None => value,
+        };
+
+        let mut next = Some((seed, next));
+        steps.step(self.step, value, &mut |value, _| {
+            let (seed, next) = next.take().expect("attempted to re-use builder");
+            (next.0)(seed, ctx, value)
+        })
+    }
+
+    fn apply_then(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        let seed = self.seed.take().expect("attempted to re-use builder");
+        let next = self.next.take().expect("attempted to re-use builder");
+        let step = self.step;
+
+        let mut next = Some((seed, next));
+        apply_previous(
+            self.previous.as_mut(),
+            ctx,
+            value,
+            steps,
+            &mut |value, steps| {
+                let value = steps.step(step, value, &mut |value, _| {
+                    let (seed, next) = next.take().expect("attempted to re-use builder");
+                    (next.0)(seed, ctx, value)
+                });
+
+                then(value, steps)
+            },
+        )
+    }
+
+    fn has_around(&self) -> bool {
+        self.previous_has_around::<TContext>()
+    }
+
+    fn set_label(&mut self, label: &'static str) {
+        StatefulApply::set_label(self, label)
+    }
+}
+
+impl<TSeed, TValue, TContext: ?Sized, TPreviousMethod, TNextMethod> Method<TValue, TContext>
+    for StatefulApply<TSeed, TValue, TPreviousMethod, ByRefMutCtx<TNextMethod>>
+where
+    TPreviousMethod: Method<TValue, TContext>,
+    TNextMethod: FnOnce(TSeed, &TContext, &mut TValue),
+{
+    fn apply(&mut self, ctx: &TContext, value: TValue) -> TValue {
+        if self.has_around() {
+            return self.apply_then(ctx, value, &mut Direct, &mut |value, _| value);
+        }
+
+        let seed = self.seed.take().expect("attempted to re-use builder");
+        let next = self.next.take().expect("attempted to re-use builder");
+
+        let value = match self.previous {
+            Some(ref mut previous) => previous.apply(ctx, value),
+            None => value,
+        };
+
+        let mut value = value;
+        (next.0)(seed, ctx, &mut value);
+        value
+    }
+
+    fn apply_steps(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+    ) -> TValue {
+        if self.has_around() {
+            return self.apply_then(ctx, value, steps, &mut |value, _| value);
+        }
+
+        let seed = self.seed.take().expect("attempted to re-use builder");
+        let next = self.next.take().expect("attempted to re-use builder");
+
+        let value = match self.previous {
+            Some(ref mut previous) => previous.apply_steps(ctx, value, steps),
+            None => value,
+        };
+
+        let mut next = Some((seed, next));
+        steps.step(self.step, value, &mut |value, _| {
+            let (seed, next) = next.take().expect("attempted to re-use builder");
+            let mut value = value;
+            (next.0)(seed, ctx, &mut value);
+            value
+        })
+    }
+
+    fn apply_then(
+        &mut self,
+        ctx: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        let seed = self.seed.take().expect("attempted to re-use builder");
+        let next = self.next.take().expect("attempted to re-use builder");
+        let step = self.step;
+
+        let mut next = Some((seed, next));
+        apply_previous(
+            self.previous.as_mut(),
+            ctx,
+            value,
+            steps,
+            &mut |value, steps| {
+                let value = steps.step(step, value, &mut |mut value, _| {
+                    let (seed, next) = next.take().expect("attempted to re-use builder");
+                    (next.0)(seed, ctx, &mut value);
+                    value
+                });
+
+                then(value, steps)
+            },
+        )
+    }
+
+    fn has_around(&self) -> bool {
+        self.previous_has_around::<TContext>()
+    }
+
+    fn set_label(&mut self, label: &'static str) {
+        StatefulApply::set_label(self, label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use imp::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Env {
+        Dev,
+        Prod,
+    }
+
+    struct Flags {
+        verbose: bool,
+    }
+
+    #[test]
+    fn default_fluent_ctx_stack() {
+        let builder = FluentBuilder::<String, Stack>::default()
+            .fluent_mut(|v| v.push_str("_f1"))
+            .fluent_ctx(|env: &Env, v| format!("{}_{:?}", v, env))
+            .boxed()
+            .fluent_ctx_mut(|env: &Env, v| v.push_str(&format!("_{:?}", env)))
+            .fluent_mut(|v| v.push_str("_f2"));
+
+        let result = builder.into_value_with(&Env::Prod, || "default".to_owned());
+
+        assert_eq!("default_f1_Prod_Prod_f2", result);
+    }
+
+    #[test]
+    fn default_fluent_ctx_override() {
+        let builder = FluentBuilder::<String>::default()
+            .fluent_ctx(|env: &Env, v| format!("{}_{:?}", v, env))
+            .fluent_ctx_mut(|env: &Env, v| v.push_str(&format!("_{:?}_f2", env)));
+
+        let result = builder.into_value_with(&Env::Dev, || "default".to_owned());
+
+        assert_eq!("default_Dev_f2", result);
+    }
+
+    #[test]
+    fn value_fluent_ctx_stack() {
+        let builder = FluentBuilder::<String, Stack>::default()
+            .value("value".to_owned())
+            .fluent_ctx_mut(|env: &Env, v| v.push_str(&format!("_{:?}", env)));
+
+        let result = builder.into_value_with(&Env::Dev, || "default".to_owned());
+
+        assert_eq!("value_Dev", result);
+    }
+
+    #[test]
+    fn value_fluent_ctx_eager() {
+        let builder = FluentBuilder::<String, Eager>::default()
+            .value("value".to_owned())
+            .fluent_mut(|v| v.push_str("_f1"))
+            .fluent_ctx_mut(|env: &Env, v| v.push_str(&format!("_{:?}", env)))
+            .fluent_mut(|v| v.push_str("_f2"));
+
+        assert!(builder.inner.as_inner().fluent_method.is_some());
+
+        let result = builder.into_value_with(&Env::Dev, || "default".to_owned());
+
+        assert_eq!("value_f1_Dev_f2", result);
+    }
+
+    #[test]
+    fn default_fluent_around_ctx() {
+        let builder = FluentBuilder::<String, Stack>::default()
+            .fluent_around(|v, next| format!("{}_done", next(v)))
+            .fluent_ctx_mut(|env: &Env, v| v.push_str(&format!("_{:?}", env)))
+            .fluent_around(|v, next| format!("{}_around", next(v)))
+            .fluent_mut(|v| v.push_str("_f3"));
+
+        let result = builder.into_value_with(&Env::Prod, || "default".to_owned());
+
+        assert_eq!("default_Prod_f3_around_done", result);
+    }
+
+    #[test]
+    fn default_fluent_ctx_shared() {
+        let builder = FluentBuilder::<String, Stack>::default()
+            .fluent_ctx_mut(|env: &Env, v| v.push_str(&format!("_{:?}", env)))
+            .shared();
+
+        let result =
+            std::thread::spawn(move || builder.into_value_with(&Env::Dev, || "default".to_owned()))
+                .join()
+                .unwrap();
+
+        assert_eq!("default_Dev", result);
+    }
+
+    #[test]
+    fn value_fluent_ctx_try_into_value_with() {
+        let builder = FluentBuilder::<String, Stack>::default()
+            .fluent_ctx_mut(|env: &Env, v| v.push_str(&format!("_{:?}", env)));
+
+        let builder = match builder.try_into_value_with(&Env::Dev) {
+            TryIntoValue::Value(_) => panic!("expected builder"),
+            TryIntoValue::Builder(builder) => builder,
+        };
+
+        let value = builder
+            .value("value".to_owned())
+            .fluent_ctx_mut(|env: &Env, v| v.push_str(&format!("_{:?}", env)))
+            .try_into_value_with(&Env::Prod)
+            .value();
+
+        assert_eq!(Some("value_Prod".to_owned()), value);
+    }
+
+    #[test]
+    fn default_fluent_ctx_apply_to_with() {
+        let mut target = "target".to_owned();
+
+        FluentBuilder::<String, Stack>::default()
+            .fluent_mut(|v| v.push_str("_f1"))
+            .fluent_ctx_mut(|env: &Env, v| v.push_str(&format!("_{:?}", env)))
+            .apply_to_with(&Env::Prod, &mut target);
+
+        assert_eq!("target_f1_Prod", target);
+    }
+
+    #[test]
+    fn nested_fluent_ctx() {
+        let builder = FluentBuilder::<String, Stack>::default().fluent_ctx_mut(|env: &Env, v| {
+            let inner = FluentBuilder::<String, Stack>::default()
+                .fluent_ctx_mut(|flags: &Flags, v| {
+                    if flags.verbose {
+                        v.push_str("_verbose");
+                    }
+                })
+                .into_value_with(&Flags { verbose: true }, || "inner".to_owned());
+
+            v.push_str(&format!("_{:?}_{}", env, inner));
+        });
+
+        let result = builder.into_value_with(&Env::Prod, || "default".to_owned());
+
+        assert_eq!("default_Prod_inner_verbose", result);
+    }
+
+    #[test]
+    fn stateful_fluent_ctx_stack() {
+        let builder = StatefulFluentBuilder::<&str, String, Stack>::from_seed("seed")
+            .fluent_mut("_f1", |s, v| v.push_str(s))
+            .fluent_ctx("_f2", |s, env: &Env, v| format!("{}{}_{:?}", v, s, env))
+            .fluent_ctx_mut("_f3", |s, env: &Env, v| {
+                v.push_str(&format!("{}_{:?}", s, env))
+            });
+
+        let result = builder.into_value_with(&Env::Dev, |seed| seed.to_owned());
+
+        assert_eq!("seed_f1_f2_Dev_f3_Dev", result);
+    }
+
+    #[test]
+    fn stateful_fluent_ctx_override() {
+        let builder = StatefulFluentBuilder::<&str, String>::from_seed("seed")
+            .fluent_mut("f1", |v| v.push_str("_f1"))
+            .fluent_ctx("f2", |env: &Env, v| format!("{}_{:?}", v, env))
+            .fluent_ctx_mut("f3", |env: &Env, v| v.push_str(&format!("_{:?}", env)))
+            .boxed();
+
+        let result = builder.into_value_with(&Env::Prod, |seed| seed.to_owned());
+
+        assert_eq!("f3_Prod", result);
+    }
+}
@@ -0,0 +1,177 @@
+/*!
+Flag builders that are dropped without being converted into a value.
+
+A builder that has collected a value or fluent methods but is never built is usually a bug,
+like a forgotten branch in a wrapping builder.
+When the `debug-drop-check` feature is enabled, these builders are reported as warnings through `log`, so dropping one never panics.
+Builders that are dropped deliberately should be consumed using `discard` instead.
+*/
+
+#[cfg(feature = "debug-drop-check")]
+use std::any;
+use std::mem::ManuallyDrop;
+use std::ptr;
+#[cfg(feature = "debug-drop-check")]
+use std::thread;
+
+#[cfg(feature = "debug-drop-check")]
+use super::logging::TARGET;
+#[cfg(feature = "debug-drop-check")]
+use super::State;
+use super::{StatefulFluentBuilderInner, Storage};
+
+/**
+The state of a builder that's checked when it's dropped.
+
+The `Drop` impl always exists, so enabling the `debug-drop-check` feature doesn't change which builders compile.
+It only does any work when that feature is enabled.
+*/
+pub(super) struct DropCheck<TSeed, TValue, TStorage, TContext: ?Sized>(
+    StatefulFluentBuilderInner<TSeed, TValue, TStorage, TContext>,
+)
+where
+    TStorage: Storage<TValue, TContext>;
+
+impl<TSeed, TValue, TStorage, TContext: ?Sized> DropCheck<TSeed, TValue, TStorage, TContext>
+where
+    TStorage: Storage<TValue, TContext>,
+{
+    pub(super) fn new(
+        inner: StatefulFluentBuilderInner<TSeed, TValue, TStorage, TContext>,
+    ) -> Self {
+        DropCheck(inner)
+    }
+
+    pub(super) fn get(&self) -> &StatefulFluentBuilderInner<TSeed, TValue, TStorage, TContext> {
+        &self.0
+    }
+
+    pub(super) fn get_mut(
+        &mut self,
+    ) -> &mut StatefulFluentBuilderInner<TSeed, TValue, TStorage, TContext> {
+        &mut self.0
+    }
+
+    /**
+    Take the state out of the builder because it's being consumed, so it isn't checked.
+    */
+    pub(super) fn into_inner(
+        self,
+    ) -> StatefulFluentBuilderInner<TSeed, TValue, TStorage, TContext> {
+        let check = ManuallyDrop::new(self);
+
+        // The state is read out exactly once and the check is never dropped,
+        // so the state isn't dropped twice
+        unsafe { ptr::read(&check.0) }
+    }
+}
+
+impl<TSeed, TValue, TStorage, TContext: ?Sized> Drop
+    for DropCheck<TSeed, TValue, TStorage, TContext>
+where
+    TStorage: Storage<TValue, TContext>,
+{
+    fn drop(&mut self) {
+        #[cfg(feature = "debug-drop-check")]
+        {
+            // The builder is being dropped while it's already unwinding, so it was never going to be built
+            if thread::panicking() {
+                return;
+            }
+
+            let has_value = match self.0.state {
+                State::Value(_) => true,
+                State::Seed(_) => false,
+            };
+
+            if has_value || self.0.fluent_method.is_some() {
+                warn!(
+                    target: TARGET,
+                    "a builder for `{}` with a value or pending fluent methods was dropped without being converted into a value (use `discard` if this was intended)",
+                    any::type_name::<TValue>()
+                );
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "debug-drop-check"))]
+mod tests {
+    use std::panic;
+
+    use imp::logging::capture::capture;
+    use imp::*;
+
+    const DROPPED: &str = "dropped without being converted into a value";
+
+    #[test]
+    fn drop_default() {
+        let events = capture(|| {
+            let _ = FluentBuilder::<String, Stack>::default();
+        });
+
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn discard_fluent() {
+        let events = capture(|| {
+            FluentBuilder::<String, Stack>::default()
+                .fluent_mut(|v| v.push_str("_f1"))
+                .discard();
+        });
+
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn override_value_fluent() {
+        let events = capture(|| {
+            let result = FluentBuilder::<String>::default()
+                .value("value".to_owned())
+                .fluent_mut(|v| v.push_str("_f1"))
+                .fluent_mut(|v| v.push_str("_f2"))
+                .into_value(|| "default".to_owned());
+
+            assert_eq!("default_f2", result);
+        });
+
+        assert!(events.iter().all(|event| !event.contains(DROPPED)));
+    }
+
+    #[test]
+    fn drop_fluent() {
+        let events = capture(|| {
+            let _ = FluentBuilder::<String, Stack>::default().fluent_mut(|v| v.push_str("_f1"));
+        });
+
+        assert_eq!(1, events.len());
+        assert!(events[0].contains(DROPPED));
+    }
+
+    #[test]
+    fn drop_stateful_value() {
+        let events = capture(|| {
+            let _ = StatefulFluentBuilder::<i32, String, Stack>::from_value("value".to_owned());
+        });
+
+        assert_eq!(1, events.len());
+        assert!(events[0].contains(DROPPED));
+    }
+
+    #[test]
+    fn drop_fluent_unwinding() {
+        let events = capture(|| {
+            let result = panic::catch_unwind(|| {
+                let _builder =
+                    FluentBuilder::<String, Stack>::default().fluent_mut(|v| v.push_str("_f1"));
+
+                panic!("explicit panic");
+            });
+
+            assert!(result.is_err());
+        });
+
+        assert!(events.is_empty());
+    }
+}
@@ -0,0 +1,341 @@
+/*!
+Type-erased builders that can be stored together, regardless of the type of value they build.
+*/
+
+use std::any::{self, Any, TypeId};
+use std::fmt;
+
+use super::{Boxed, FluentBuilder, Shared, State, Storage};
+
+/**
+An object-safe fluent builder.
+
+`DynBuilder` erases the type of value a builder produces, so builders for unrelated types
+can be stored together as `Box<dyn DynBuilder>`.
+It's implemented for builders with `Boxed` or `Shared` storage, because their type doesn't depend on their fluent methods.
+Use `AnyFluentBuilder` to get the original builder back out.
+
+# Examples
+
+```
+use std::any::{Any, TypeId};
+use fluent_builder::{DynBuilder, FluentBuilder, Stack};
+
+let builders: Vec<Box<dyn DynBuilder>> = vec![
+    Box::new(FluentBuilder::<String, Stack>::default().fluent_mut(|s| s.push_str(" and more")).boxed()),
+    Box::new(FluentBuilder::<i32>::default().value(1).boxed()),
+];
+
+assert_eq!(TypeId::of::<String>(), builders[0].value_type_id());
+
+let values: Vec<Box<dyn Any>> = builders
+    .into_iter()
+    .zip(vec![Box::new("A value".to_owned()) as Box<dyn Any>, Box::new(0)])
+    .map(|(builder, default)| builder.build_any(default))
+    .collect();
+
+assert_eq!("A value and more", values[0].downcast_ref::<String>().unwrap());
+assert_eq!(1, *values[1].downcast_ref::<i32>().unwrap());
+```
+
+Builders with inline fluent methods need to be boxed first:
+
+```compile_fail
+use fluent_builder::{DynBuilder, FluentBuilder, Stack};
+
+let builder: Box<dyn DynBuilder> = Box::new(FluentBuilder::<String, Stack>::default().fluent_mut(|s| s.push_str(" and more")));
+```
+*/
+pub trait DynBuilder {
+    /**
+    Whether the builder contains a value, so a default won't be needed to build it.
+    */
+    fn has_value(&self) -> bool;
+
+    /**
+    The `TypeId` of the value the builder produces.
+    */
+    fn value_type_id(&self) -> TypeId;
+
+    /**
+    The name of the type of value the builder produces, for diagnostics.
+    */
+    fn value_type_name(&self) -> &'static str;
+
+    /**
+    Convert the builder into a value, using the given default if the builder doesn't contain one.
+
+    This method behaves the same as `into_value`, but the default and the result are boxed.
+
+    # Panics
+
+    This method will panic if the default isn't a value of the type the builder produces.
+    */
+    fn build_any(self: Box<Self>, default_value: Box<dyn Any>) -> Box<dyn Any>;
+
+    /**
+    Drop the builder without converting it into a value.
+
+    This method behaves the same as `discard`.
+    */
+    fn discard(self: Box<Self>);
+}
+
+/**
+Storage for fluent methods that doesn't change type as methods are added to it.
+*/
+pub trait DynStorage<TValue>: Storage<TValue> {}
+
+impl<TValue> DynStorage<TValue> for Boxed {}
+
+impl<TValue> DynStorage<TValue> for Shared {}
+
+impl<TValue, TStack, TStorage> DynBuilder for FluentBuilder<TValue, TStack, TStorage>
+where
+    TValue: 'static,
+    TStack: 'static,
+    TStorage: DynStorage<TValue> + 'static,
+{
+    fn has_value(&self) -> bool {
+        match self.inner.as_inner().state {
+            State::Value(_) => true,
+            State::Seed(()) => false,
+        }
+    }
+
+    fn value_type_id(&self) -> TypeId {
+        TypeId::of::<TValue>()
+    }
+
+    fn value_type_name(&self) -> &'static str {
+        any::type_name::<TValue>()
+    }
+
+    fn build_any(self: Box<Self>, default_value: Box<dyn Any>) -> Box<dyn Any> {
+        let default_value = match default_value.downcast::<TValue>() {
+            Ok(default_value) => *default_value,
+            Err(_) => panic!(
+                "the default value for a builder of `{}` has a different type",
+                any::type_name::<TValue>()
+            ),
+        };
+
+        Box::new((*self).into_value(move || default_value))
+    }
+
+    fn discard(self: Box<Self>) {
+        FluentBuilder::discard(*self)
+    }
+}
+
+trait AnyBuilder: DynBuilder {
+    fn as_any(&self) -> &dyn Any;
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+
+    fn into_dyn(self: Box<Self>) -> Box<dyn DynBuilder>;
+}
+
+impl<TBuilder> AnyBuilder for TBuilder
+where
+    TBuilder: DynBuilder + Any,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn into_dyn(self: Box<Self>) -> Box<dyn DynBuilder> {
+        self
+    }
+}
+
+/**
+A type-erased `FluentBuilder` that can be downcast back to its original type.
+
+# Examples
+
+```
+use fluent_builder::{AnyFluentBuilder, Boxed, BoxedFluentBuilder, FluentBuilder, Stack};
+
+let builder = AnyFluentBuilder::new(
+    FluentBuilder::<String, Stack>::default()
+        .fluent_mut(|s| s.push_str(" and more"))
+        .boxed(),
+);
+
+assert!(!builder.is::<i32, Stack, Boxed>());
+
+let builder: BoxedFluentBuilder<String, Stack> = builder.downcast().unwrap();
+
+assert_eq!("A value and more", builder.into_value(|| "A value".to_owned()));
+```
+*/
+#[must_use = "builders do nothing unless they're converted into a value"]
+pub struct AnyFluentBuilder {
+    inner: Box<dyn AnyBuilder>,
+}
+
+impl AnyFluentBuilder {
+    /**
+    Erase the type of the given builder.
+    */
+    pub fn new<TValue, TStack, TStorage>(builder: FluentBuilder<TValue, TStack, TStorage>) -> Self
+    where
+        TValue: 'static,
+        TStack: 'static,
+        TStorage: DynStorage<TValue> + 'static,
+    {
+        AnyFluentBuilder {
+            inner: Box::new(builder),
+        }
+    }
+
+    /**
+    Whether the builder is a `FluentBuilder<TValue, TStack, TStorage>`.
+    */
+    pub fn is<TValue, TStack, TStorage>(&self) -> bool
+    where
+        TValue: 'static,
+        TStack: 'static,
+        TStorage: Storage<TValue> + 'static,
+    {
+        self.inner
+            .as_any()
+            .is::<FluentBuilder<TValue, TStack, TStorage>>()
+    }
+
+    /**
+    Get the original builder back.
+
+    If the builder isn't a `FluentBuilder<TValue, TStack, TStorage>` then it's returned unchanged.
+    */
+    pub fn downcast<TValue, TStack, TStorage>(
+        self,
+    ) -> Result<FluentBuilder<TValue, TStack, TStorage>, Self>
+    where
+        TValue: 'static,
+        TStack: 'static,
+        TStorage: Storage<TValue> + 'static,
+    {
+        if self.is::<TValue, TStack, TStorage>() {
+            let builder = self
+                .inner
+                .into_any()
+                .downcast::<FluentBuilder<TValue, TStack, TStorage>>()
+                .expect("the builder has a different type");
+
+            Ok(*builder)
+        } else {
+            Err(self)
+        }
+    }
+
+    /**
+    Get the type-erased builder.
+    */
+    pub fn into_dyn(self) -> Box<dyn DynBuilder> {
+        self.inner.into_dyn()
+    }
+}
+
+impl DynBuilder for AnyFluentBuilder {
+    fn has_value(&self) -> bool {
+        self.inner.has_value()
+    }
+
+    fn value_type_id(&self) -> TypeId {
+        self.inner.value_type_id()
+    }
+
+    fn value_type_name(&self) -> &'static str {
+        self.inner.value_type_name()
+    }
+
+    fn build_any(self: Box<Self>, default_value: Box<dyn Any>) -> Box<dyn Any> {
+        self.inner.build_any(default_value)
+    }
+
+    fn discard(self: Box<Self>) {
+        self.inner.discard()
+    }
+}
+
+impl fmt::Debug for AnyFluentBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AnyFluentBuilder")
+            .field("value_type", &self.inner.value_type_name())
+            .field("has_value", &self.inner.has_value())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::{Any, TypeId};
+
+    use imp::*;
+
+    #[test]
+    fn default_build_any() {
+        let builder: Box<dyn DynBuilder> = Box::new(
+            FluentBuilder::<String, Stack>::default()
+                .fluent_mut(|v| v.push_str("_f1"))
+                .shared(),
+        );
+
+        assert!(!builder.has_value());
+        assert_eq!(TypeId::of::<String>(), builder.value_type_id());
+
+        let result = builder.build_any(Box::new("default".to_owned()));
+
+        assert_eq!("default_f1", result.downcast_ref::<String>().unwrap());
+    }
+
+    #[test]
+    fn value_build_any() {
+        let builder: Box<dyn DynBuilder> =
+            Box::new(SharedFluentBuilder::<String>::default().value("value".to_owned()));
+
+        assert!(builder.has_value());
+
+        let result = builder.build_any(Box::new("default".to_owned()));
+
+        assert_eq!("value", result.downcast_ref::<String>().unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "has a different type")]
+    fn default_build_any_wrong_type() {
+        let builder: Box<dyn DynBuilder> = Box::new(BoxedFluentBuilder::<String>::default());
+
+        builder.build_any(Box::new(1) as Box<dyn Any>);
+    }
+
+    #[test]
+    fn any_downcast() {
+        let builder = AnyFluentBuilder::new(
+            FluentBuilder::<String, Stack>::default()
+                .fluent_mut(|v| v.push_str("_f1"))
+                .boxed(),
+        );
+
+        assert_eq!(TypeId::of::<String>(), builder.value_type_id());
+
+        let debug = format!("{:?}", builder);
+        assert!(debug.starts_with("AnyFluentBuilder { value_type: "));
+        assert!(debug.contains("String"));
+        assert!(debug.ends_with(", has_value: false }"));
+
+        let builder = match builder.downcast::<String, Override, Boxed>() {
+            Ok(_) => panic!("expected a different type"),
+            Err(builder) => builder,
+        };
+        let builder = builder.downcast::<String, Stack, Boxed>().unwrap();
+
+        assert_eq!("default_f1", builder.into_value(|| "default".to_owned()));
+    }
+}
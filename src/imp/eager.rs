@@ -0,0 +1,288 @@
+/*!
+Fluent methods that are applied as soon as they're added, if the builder already contains a value.
+
+When the builder contains a value there's no need to store its fluent methods,
+so they're applied immediately instead of being boxed and nested in the builder.
+Methods are only stored while the builder is waiting on a default value.
+*/
+
+use std::panic::Location;
+
+use super::{
+    apply_method, Apply, Around, Base, ByRefMut, ByValue, Eager, Inline, Method, State,
+    StatefulApply, StatefulFluentBuilder, StatefulFluentBuilderInner, Storage,
+};
+
+/**
+A fluent method that can be applied as soon as it's added to a builder that contains a value.
+
+Methods that need a context are given back along with the value,
+because there's no context to give them until the value is built.
+*/
+pub trait Immediate<TValue>: Sized {
+    fn apply_immediately(
+        self,
+        value: TValue,
+        location: &'static Location<'static>,
+    ) -> Result<TValue, (Self, TValue)>;
+}
+
+impl<TValue, TNextMethod> Immediate<TValue> for ByValue<TNextMethod>
+where
+    TNextMethod: FnOnce(TValue) -> TValue,
+{
+    fn apply_immediately(
+        self,
+        value: TValue,
+        location: &'static Location<'static>,
+    ) -> Result<TValue, (Self, TValue)> {
+        Ok(apply_immediately(self, value, location))
+    }
+}
+
+impl<TValue, TNextMethod> Immediate<TValue> for ByRefMut<TNextMethod>
+where
+    TNextMethod: FnOnce(&mut TValue),
+{
+    fn apply_immediately(
+        self,
+        value: TValue,
+        location: &'static Location<'static>,
+    ) -> Result<TValue, (Self, TValue)> {
+        Ok(apply_immediately(self, value, location))
+    }
+}
+
+impl<TValue, TNextMethod> Immediate<TValue> for Around<TNextMethod>
+where
+    TNextMethod: FnOnce(TValue, &mut dyn FnMut(TValue) -> TValue) -> TValue,
+{
+    fn apply_immediately(
+        self,
+        value: TValue,
+        location: &'static Location<'static>,
+    ) -> Result<TValue, (Self, TValue)> {
+        Ok(apply_immediately(self, value, location))
+    }
+}
+
+fn apply_immediately<TValue, TNextMethod>(
+    fluent_method: TNextMethod,
+    value: TValue,
+    location: &'static Location<'static>,
+) -> TValue
+where
+    Apply<TValue, Inline, TNextMethod>: Method<TValue>,
+{
+    let mut fluent_method = Apply::<TValue, Inline, _>::new(None, fluent_method, location);
+
+    apply_method(&mut fluent_method, &(), value, Base::Value)
+}
+
+impl<TSeed, TValue, TStorage> StatefulFluentBuilder<TSeed, TValue, Eager, TStorage>
+where
+    TStorage: Storage<TValue>,
+{
+    fn eager<TNextMethod>(
+        self,
+        seed: TSeed,
+        fluent_method: TNextMethod,
+        location: &'static Location<'static>,
+    ) -> StatefulFluentBuilder<
+        TSeed,
+        TValue,
+        Eager,
+        StatefulApply<TSeed, TValue, TStorage::Method, TNextMethod>,
+    >
+    where
+        StatefulApply<TSeed, TValue, TStorage::Method, TNextMethod>: Method<TValue>,
+    {
+        let StatefulFluentBuilderInner {
+            state,
+            fluent_method: previous_fluent_method,
+        } = self.into_inner();
+
+        let mut fluent_method =
+            StatefulApply::new(seed, previous_fluent_method, fluent_method, location);
+
+        match state {
+            State::Value(value) => StatefulFluentBuilder::from_value(apply_method(
+                &mut fluent_method,
+                &(),
+                value,
+                Base::Value,
+            )),
+            state => StatefulFluentBuilder::new(StatefulFluentBuilderInner {
+                state,
+                fluent_method: Some(fluent_method),
+            }),
+        }
+    }
+
+    /**
+    Apply a fluent method to the value in the builder, or stack it if there's no value yet.
+
+    This will have the following behaviour depending on the current state of the builder if there is:
+
+    - a previous value, apply the fluent method to it immediately.
+    - no previous value, stack the fluent method on top of any previous ones. These will be applied to a later-supplied default value.
+
+    If the method panics while being applied immediately then the builder is dropped.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn fluent<TNextMethod>(
+        self,
+        seed: TSeed,
+        fluent_method: TNextMethod,
+    ) -> StatefulFluentBuilder<
+        TSeed,
+        TValue,
+        Eager,
+        StatefulApply<TSeed, TValue, TStorage::Method, ByValue<TNextMethod>>,
+    >
+    where
+        TNextMethod: FnOnce(TSeed, TValue) -> TValue,
+    {
+        self.eager(seed, ByValue(fluent_method), Location::caller())
+    }
+
+    /**
+    Apply a fluent method to the value in the builder, or stack it if there's no value yet.
+
+    This method behaves the same as `fluent`, but mutates the value instead of replacing it.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn fluent_mut<TNextMethod>(
+        self,
+        seed: TSeed,
+        fluent_method: TNextMethod,
+    ) -> StatefulFluentBuilder<
+        TSeed,
+        TValue,
+        Eager,
+        StatefulApply<TSeed, TValue, TStorage::Method, ByRefMut<TNextMethod>>,
+    >
+    where
+        TNextMethod: FnOnce(TSeed, &mut TValue),
+    {
+        self.eager(seed, ByRefMut(fluent_method), Location::caller())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use imp::*;
+
+    #[test]
+    fn default_fluent_eager() {
+        let builder = BoxedFluentBuilder::<String, Eager>::default()
+            .fluent_mut(|v| v.push_str("_f1"))
+            .fluent(|v| format!("{}_f2", v));
+
+        assert!(builder.inner.as_inner().fluent_method.is_some());
+
+        let result = builder.into_value(|| "default".to_owned());
+
+        assert_eq!("default_f1_f2", result);
+    }
+
+    #[test]
+    fn value_fluent_eager_applies_immediately() {
+        let applied = Rc::new(Cell::new(false));
+
+        let builder = BoxedFluentBuilder::<String, Eager>::default()
+            .value("value".to_owned())
+            .fluent_mut({
+                let applied = applied.clone();
+                move |v| {
+                    applied.set(true);
+                    v.push_str("_f1");
+                }
+            })
+            .fluent_ref(|v| {
+                v.push_str("_f2");
+                v
+            });
+
+        assert!(applied.get());
+        assert!(builder.inner.as_inner().fluent_method.is_none());
+
+        let result = builder.into_value(|| "default".to_owned());
+
+        assert_eq!("value_f1_f2", result);
+    }
+
+    #[test]
+    fn value_fluent_eager_inline() {
+        let builder = FluentBuilder::<String, Eager>::default()
+            .value("value".to_owned())
+            .fluent_mut(|v| v.push_str("_f1"))
+            .fluent_around(|v, next| format!("{}_around", next(v)));
+
+        assert!(builder.inner.as_inner().fluent_method.is_none());
+
+        let result = builder.into_value(|| "default".to_owned());
+
+        assert_eq!("value_f1_around", result);
+    }
+
+    #[test]
+    fn stateful_fluent_eager_shared() {
+        let builder = SharedStatefulFluentBuilder::<i32, String, Eager>::from_seed(1)
+            .fluent(2, |s, v| format!("{}_f{}", v, s))
+            .value("value".to_owned())
+            .fluent_mut(3, |s, v| v.push_str(&format!("_f{}", s)));
+
+        assert!(builder.as_inner().fluent_method.is_none());
+
+        let result = builder.into_value(|s| format!("default_{}", s));
+
+        assert_eq!("value_f3", result);
+    }
+
+    #[test]
+    fn stateful_fluent_eager_inline() {
+        let builder = StatefulFluentBuilder::<i32, String, Eager>::from_value("value".to_owned())
+            .fluent(1, |s, v| format!("{}_f{}", v, s))
+            .fluent_mut(2, |s, v| v.push_str(&format!("_f{}", s)));
+
+        assert!(builder.as_inner().fluent_method.is_none());
+
+        let result = builder.into_value(|s| format!("default_{}", s));
+
+        assert_eq!("value_f1_f2", result);
+    }
+
+    #[test]
+    fn value_stack_compact() {
+        let builder = FluentBuilder::<String, Stack>::default()
+            .value("value".to_owned())
+            .fluent_mut(|v| v.push_str("_f1"))
+            .boxed()
+            .compact();
+
+        assert!(builder.inner.as_inner().fluent_method.is_none());
+
+        let result = builder.into_value(|| "default".to_owned());
+
+        assert_eq!("value_f1", result);
+    }
+
+    #[test]
+    fn default_stack_compact() {
+        let builder = FluentBuilder::<String, Stack>::default()
+            .fluent_mut(|v| v.push_str("_f1"))
+            .compact();
+
+        let result = builder.into_value(|| "default".to_owned());
+
+        assert_eq!("default_f1", result);
+    }
+}
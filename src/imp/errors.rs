@@ -0,0 +1,437 @@
+/*!
+Errors collected from trees of nested builders, tagged with the path of the builder they came from.
+*/
+
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+
+use super::{FluentBuilder, StatefulFluentBuilder, Storage};
+
+/**
+The location of a builder within a tree of nested builders, used as the context for its fluent methods.
+
+Context-aware fluent methods that accept a `&BuildPath` can push errors to it.
+Paths for nested fields and items can be created using `field` and `index`,
+and errors from nested builders can be pushed using `push_errors`.
+A builder is converted into a value at a path using `into_value_in`,
+which returns all of the errors pushed while building it instead of failing on the first.
+
+# Examples
+
+```
+use fluent_builder::{BuildPath, FluentBuilder, Stack};
+
+#[derive(Debug, Default)]
+struct Request {
+    headers: Vec<String>,
+}
+
+let request = FluentBuilder::<Request, Stack>::default()
+    .fluent_mut(|r| r.headers.push("Accept: text/plain".to_owned()))
+    .fluent_mut(|r| r.headers.push("Accept".to_owned()))
+    .fluent_ctx_mut(|path: &BuildPath, r| {
+        let headers = path.field("headers");
+
+        for (i, header) in r.headers.iter().enumerate() {
+            if !header.contains(':') {
+                headers.index(i).push_error("missing a `:`");
+            }
+        }
+    })
+    .into_value_in("request", Request::default);
+
+let err = request.unwrap_err();
+
+assert_eq!("request.headers[1]", err.errors()[0].path());
+```
+*/
+pub struct BuildPath {
+    path: String,
+    errors: RefCell<Vec<BuildError>>,
+}
+
+/**
+The location of a field or item within a `BuildPath`.
+
+Errors pushed to it are collected by the `BuildPath` it came from.
+*/
+pub struct BuildPathRef<'a> {
+    path: String,
+    errors: &'a RefCell<Vec<BuildError>>,
+}
+
+/**
+An error from a builder, along with the path to it.
+*/
+pub struct BuildError {
+    path: String,
+    error: Box<dyn Error + Send + Sync>,
+}
+
+/**
+All of the errors collected from a tree of nested builders.
+*/
+#[derive(Debug)]
+pub struct BuildErrors {
+    errors: Vec<BuildError>,
+}
+
+impl BuildPath {
+    fn new(path: &str) -> Self {
+        BuildPath {
+            path: path.to_owned(),
+            errors: RefCell::new(Vec::new()),
+        }
+    }
+
+    /**
+    The full path to the builder, like `request.body.headers[2]`.
+    */
+    pub fn as_str(&self) -> &str {
+        &self.path
+    }
+
+    /**
+    Get the path to a named field of this builder.
+    */
+    pub fn field(&self, name: &str) -> BuildPathRef<'_> {
+        BuildPathRef {
+            path: field(&self.path, name),
+            errors: &self.errors,
+        }
+    }
+
+    /**
+    Get the path to an item in a sequence at this builder.
+    */
+    pub fn index(&self, index: usize) -> BuildPathRef<'_> {
+        BuildPathRef {
+            path: format!("{}[{}]", self.path, index),
+            errors: &self.errors,
+        }
+    }
+
+    /**
+    Push an error for the builder at this path.
+    */
+    pub fn push_error<TError>(&self, error: TError)
+    where
+        TError: Into<Box<dyn Error + Send + Sync>>,
+    {
+        push_error(&self.errors, &self.path, error.into());
+    }
+
+    /**
+    Push the errors from a nested builder.
+
+    The errors keep the paths they were pushed with.
+    */
+    pub fn push_errors(&self, errors: BuildErrors) {
+        self.errors.borrow_mut().extend(errors.errors);
+    }
+
+    fn into_result<TValue>(self, value: TValue) -> Result<TValue, BuildErrors> {
+        let errors = self.errors.into_inner();
+
+        if errors.is_empty() {
+            Ok(value)
+        } else {
+            Err(BuildErrors { errors })
+        }
+    }
+}
+
+impl fmt::Debug for BuildPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("BuildPath").field(&self.path).finish()
+    }
+}
+
+impl<'a> BuildPathRef<'a> {
+    /**
+    The full path to the field or item, like `request.body.headers[2]`.
+    */
+    pub fn as_str(&self) -> &str {
+        &self.path
+    }
+
+    /**
+    Get the path to a named field of this field or item.
+    */
+    pub fn field(&self, name: &str) -> BuildPathRef<'a> {
+        BuildPathRef {
+            path: field(&self.path, name),
+            errors: self.errors,
+        }
+    }
+
+    /**
+    Get the path to an item in a sequence at this field or item.
+    */
+    pub fn index(&self, index: usize) -> BuildPathRef<'a> {
+        BuildPathRef {
+            path: format!("{}[{}]", self.path, index),
+            errors: self.errors,
+        }
+    }
+
+    /**
+    Push an error for the field or item at this path.
+    */
+    pub fn push_error<TError>(&self, error: TError)
+    where
+        TError: Into<Box<dyn Error + Send + Sync>>,
+    {
+        push_error(self.errors, &self.path, error.into());
+    }
+
+    /**
+    Push the errors from a nested builder.
+
+    The errors keep the paths they were pushed with.
+    */
+    pub fn push_errors(&self, errors: BuildErrors) {
+        self.errors.borrow_mut().extend(errors.errors);
+    }
+}
+
+impl<'a> fmt::Debug for BuildPathRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("BuildPathRef").field(&self.path).finish()
+    }
+}
+
+fn field(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+fn push_error(errors: &RefCell<Vec<BuildError>>, path: &str, error: Box<dyn Error + Send + Sync>) {
+    errors.borrow_mut().push(BuildError {
+        path: path.to_owned(),
+        error,
+    });
+}
+
+impl BuildError {
+    /**
+    The path to the builder the error came from.
+    */
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /**
+    The underlying error.
+    */
+    pub fn error(&self) -> &(dyn Error + Send + Sync + 'static) {
+        &*self.error
+    }
+}
+
+impl fmt::Debug for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BuildError")
+            .field("path", &self.path)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            fmt::Display::fmt(&self.error, f)
+        } else {
+            write!(f, "{}: {}", self.path, self.error)
+        }
+    }
+}
+
+impl Error for BuildError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.error)
+    }
+}
+
+impl BuildErrors {
+    /**
+    The errors, in the order they were pushed.
+    */
+    pub fn errors(&self) -> &[BuildError] {
+        &self.errors
+    }
+
+    /**
+    Take the errors, in the order they were pushed.
+    */
+    pub fn into_errors(self) -> Vec<BuildError> {
+        self.errors
+    }
+}
+
+impl fmt::Display for BuildErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "failed to build a value with {} errors",
+            self.errors.len()
+        )?;
+
+        for error in &self.errors {
+            write!(f, "\n- {}", error)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for BuildErrors {}
+
+impl<TValue, TStack, TStorage> FluentBuilder<TValue, TStack, TStorage, BuildPath>
+where
+    TStorage: Storage<TValue, BuildPath>,
+{
+    /**
+    Convert the fluent builder into a value at the given path in a tree of nested builders.
+
+    This method behaves the same as `into_value_with`, using a `BuildPath` as the context.
+    If any errors were pushed to the path while the value was built then they're all returned instead of the value.
+    */
+    pub fn into_value_in<TDefault>(
+        self,
+        path: &str,
+        default_value: TDefault,
+    ) -> Result<TValue, BuildErrors>
+    where
+        TDefault: FnOnce() -> TValue,
+    {
+        let path = BuildPath::new(path);
+        let value = self.into_value_with(&path, default_value);
+
+        path.into_result(value)
+    }
+}
+
+impl<TSeed, TValue, TStack, TStorage>
+    StatefulFluentBuilder<TSeed, TValue, TStack, TStorage, BuildPath>
+where
+    TStorage: Storage<TValue, BuildPath>,
+{
+    /**
+    Convert the fluent builder into a value at the given path in a tree of nested builders.
+
+    This has the same behaviour as `FluentBuilder.into_value_in`.
+    */
+    pub fn into_value_in<TDefault>(
+        self,
+        path: &str,
+        default_value: TDefault,
+    ) -> Result<TValue, BuildErrors>
+    where
+        TDefault: FnOnce(TSeed) -> TValue,
+    {
+        let path = BuildPath::new(path);
+        let value = self.into_value_with(&path, default_value);
+
+        path.into_result(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use imp::*;
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Request {
+        host: String,
+        body: Body,
+    }
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Body {
+        headers: Vec<String>,
+    }
+
+    fn body_builder() -> FluentBuilder<Body, Stack, Boxed, BuildPath> {
+        FluentBuilder::<Body, Stack>::default()
+            .fluent_mut(|b| b.headers.push("Accept".to_owned()))
+            .fluent_mut(|b| b.headers.push("Content-Type: text/plain".to_owned()))
+            .fluent_mut(|b| b.headers.push("Host".to_owned()))
+            .fluent_ctx_mut(|path: &BuildPath, b: &mut Body| {
+                let headers = path.field("headers");
+
+                for (i, header) in b.headers.iter().enumerate() {
+                    if !header.contains(':') {
+                        headers
+                            .index(i)
+                            .push_error(format!("invalid header `{}`", header));
+                    }
+                }
+            })
+            .boxed()
+    }
+
+    #[test]
+    fn nested_into_value_in() {
+        let result = FluentBuilder::<Request, Stack>::default()
+            .fluent_ctx_mut(|path: &BuildPath, r: &mut Request| {
+                match body_builder().into_value_in(path.field("body").as_str(), Body::default) {
+                    Ok(body) => r.body = body,
+                    Err(errors) => path.push_errors(errors),
+                }
+            })
+            .fluent_ctx_mut(|path: &BuildPath, r: &mut Request| {
+                if r.host.is_empty() {
+                    path.field("host").push_error("missing host");
+                }
+            })
+            .into_value_in("request", Request::default);
+
+        let err = result.unwrap_err();
+
+        let paths: Vec<_> = err.errors().iter().map(|e| e.path()).collect();
+        assert_eq!(
+            vec![
+                "request.body.headers[0]",
+                "request.body.headers[2]",
+                "request.host"
+            ],
+            paths
+        );
+
+        assert_eq!(
+            "failed to build a value with 3 errors\n- request.body.headers[0]: invalid header `Accept`\n- request.body.headers[2]: invalid header `Host`\n- request.host: missing host",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn default_into_value_in_ok() {
+        let body = FluentBuilder::<Body, Stack, Inline, BuildPath>::default()
+            .fluent_mut(|b| b.headers.push("Host: localhost".to_owned()))
+            .into_value_in("", Body::default)
+            .unwrap();
+
+        assert_eq!(vec!["Host: localhost"], body.headers);
+    }
+
+    #[test]
+    fn stateful_into_value_in() {
+        let result = StatefulFluentBuilder::<&str, String, Stack>::from_seed("seed")
+            .fluent_ctx_mut("", |_, path: &BuildPath, v| {
+                if v.is_empty() {
+                    path.push_error("empty value");
+                }
+            })
+            .into_value_in("value", |_| String::new());
+
+        let err = result.unwrap_err();
+
+        assert_eq!("value: empty value", err.errors()[0].to_string());
+    }
+}
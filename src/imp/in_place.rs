@@ -0,0 +1,293 @@
+/*!
+Methods for mutating builders in-place, such as when they're stored in a field of another builder.
+
+Boxed and shared builders have the same type no matter how many fluent methods they contain,
+so they can be updated through a `&mut` reference instead of being moved in and out of the field.
+*/
+
+use std::mem;
+use std::panic::Location;
+
+use super::{
+    stack_method, Apply, Boxed, BoxedMethod, ByRefMut, ByValue, FluentBuilder, Immediate, Inline,
+    Method, Shared, SharedMethod, StackMode, State, StatefulFluentBuilder,
+    StatefulFluentBuilderInner, Storage,
+};
+
+impl<TValue, TStack, TStorage> FluentBuilder<TValue, TStack, TStorage>
+where
+    TStorage: Storage<TValue>,
+{
+    /**
+    Take the builder, leaving a default one in its place.
+    */
+    pub fn take(&mut self) -> Self {
+        mem::take(self)
+    }
+
+    /**
+    Set a value on the builder in-place.
+
+    This method behaves the same as `value`.
+    */
+    pub fn set_value(&mut self, value: TValue) {
+        *self = self.take().value(value);
+    }
+}
+
+impl<TValue, TStack, TStorage> FluentBuilder<TValue, TStack, TStorage>
+where
+    TStack: StackMode,
+    TStorage: Storage<TValue>,
+{
+    /**
+    Add a fluent method to the builder in-place, the same way as `fluent`.
+
+    The method is added to the end of the builder's sequence of methods using `push`, instead of wrapping them,
+    so adding many methods doesn't nest them any deeper.
+    */
+    fn push<TNextMethod, TPush>(
+        &mut self,
+        fluent_method: TNextMethod,
+        location: &'static Location<'static>,
+        push: TPush,
+    ) where
+        TNextMethod: Immediate<TValue>,
+        TPush: FnOnce(
+            Option<TStorage::Method>,
+            Apply<TValue, Inline, TNextMethod>,
+        ) -> TStorage::Method,
+    {
+        let StatefulFluentBuilderInner {
+            state,
+            fluent_method: previous_fluent_method,
+        } = self.take().inner.into_inner();
+
+        let value = match state {
+            State::Value(value) => Some(value),
+            State::Seed(()) => None,
+        };
+
+        let (value, previous_fluent_method, fluent_method) =
+            stack_method::<TStack, _, _, _>(value, previous_fluent_method, fluent_method, location);
+
+        self.inner = StatefulFluentBuilder::new(StatefulFluentBuilderInner {
+            state: value.map_or(State::Seed(()), State::Value),
+            fluent_method: fluent_method
+                .map(|next| push(previous_fluent_method, Apply::new(None, next, location))),
+        });
+    }
+}
+
+impl<TValue, TStack> FluentBuilder<TValue, TStack, Boxed>
+where
+    TValue: 'static,
+    TStack: StackMode,
+{
+    /**
+    Add a fluent method to the builder in-place.
+
+    This method behaves the same as `fluent`, but doesn't need to be given the builder by-value.
+
+    # Examples
+
+    ```
+    # use fluent_builder::{BoxedFluentBuilder, Stack};
+    #[derive(Default)]
+    struct RequestBuilder {
+        body: BoxedFluentBuilder<String, Stack>,
+    }
+
+    impl RequestBuilder {
+        fn body(&mut self, body: &str) -> &mut Self {
+            let body = body.to_owned();
+            self.body.push_fluent_mut(move |b| b.push_str(&body));
+            self
+        }
+    }
+
+    let mut request = RequestBuilder::default();
+    request.body("A value").body(" and more");
+
+    assert_eq!("A value and more", request.body.take().into_value(String::new));
+    ```
+    */
+    #[track_caller]
+    pub fn push_fluent<TNextMethod>(&mut self, fluent_method: TNextMethod)
+    where
+        TNextMethod: FnOnce(TValue) -> TValue + 'static,
+    {
+        self.push(
+            ByValue(fluent_method),
+            Location::caller(),
+            BoxedMethod::push,
+        );
+    }
+
+    /**
+    Add a fluent method to the builder in-place.
+
+    This method behaves the same as `push_fluent`, but mutates the value instead of replacing it.
+    */
+    #[track_caller]
+    pub fn push_fluent_mut<TNextMethod>(&mut self, fluent_method: TNextMethod)
+    where
+        TNextMethod: FnOnce(&mut TValue) + 'static,
+    {
+        self.push(
+            ByRefMut(fluent_method),
+            Location::caller(),
+            BoxedMethod::push,
+        );
+    }
+}
+
+impl<TValue, TStack> FluentBuilder<TValue, TStack, Shared>
+where
+    TValue: Send + 'static,
+    TStack: StackMode,
+{
+    /**
+    Add a fluent method to the builder in-place.
+
+    This method behaves the same as `fluent`, but doesn't need to be given the builder by-value.
+    */
+    #[track_caller]
+    pub fn push_fluent<TNextMethod>(&mut self, fluent_method: TNextMethod)
+    where
+        TNextMethod: FnOnce(TValue) -> TValue + Send + 'static,
+    {
+        self.push(
+            ByValue(fluent_method),
+            Location::caller(),
+            SharedMethod::push,
+        );
+    }
+
+    /**
+    Add a fluent method to the builder in-place.
+
+    This method behaves the same as `push_fluent`, but mutates the value instead of replacing it.
+    */
+    #[track_caller]
+    pub fn push_fluent_mut<TNextMethod>(&mut self, fluent_method: TNextMethod)
+    where
+        TNextMethod: FnOnce(&mut TValue) + Send + 'static,
+    {
+        self.push(
+            ByRefMut(fluent_method),
+            Location::caller(),
+            SharedMethod::push,
+        );
+    }
+}
+
+impl<TValue> BoxedMethod<TValue> {
+    fn push<TNextMethod>(previous: Option<Self>, fluent_method: TNextMethod) -> Self
+    where
+        TNextMethod: Method<TValue> + 'static,
+    {
+        let mut fluent_methods = previous.map_or_else(Vec::new, |previous| previous.0);
+        fluent_methods.push(Box::new(fluent_method));
+
+        BoxedMethod(fluent_methods)
+    }
+}
+
+impl<TValue> SharedMethod<TValue> {
+    fn push<TNextMethod>(previous: Option<Self>, fluent_method: TNextMethod) -> Self
+    where
+        TNextMethod: Method<TValue> + Send + 'static,
+    {
+        let mut fluent_methods = previous.map_or_else(Vec::new, |previous| previous.0);
+        fluent_methods.push(Box::new(fluent_method));
+
+        SharedMethod(fluent_methods)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use imp::*;
+
+    #[test]
+    fn default_push_fluent_stack() {
+        let mut builder = BoxedFluentBuilder::<String, Stack>::default();
+
+        builder.push_fluent_mut(|v| v.push_str("_f1"));
+        builder.push_fluent(|v| format!("{}_f2", v));
+
+        let result = builder.into_value(|| "default".to_owned());
+
+        assert_eq!("default_f1_f2", result);
+    }
+
+    #[test]
+    fn default_push_fluent_many() {
+        let mut builder = SharedFluentBuilder::<usize, Stack>::default();
+
+        for _ in 0..100_000 {
+            builder.push_fluent_mut(|v| *v += 1);
+        }
+        builder.push_fluent(|v| v * 2);
+
+        let result = builder.into_value(|| 0);
+
+        assert_eq!(200_000, result);
+    }
+
+    #[test]
+    fn default_push_fluent_around() {
+        let mut builder = BoxedFluentBuilder::<String, Stack>::default();
+
+        builder.push_fluent_mut(|v| v.push_str("_f1"));
+        builder.push_fluent(|v| format!("({})", v));
+
+        let mut builder = builder
+            .fluent_around(|v, next| format!("[{}]", next(v)))
+            .boxed();
+        builder.push_fluent_mut(|v| v.push_str("_f2"));
+        builder.push_fluent_mut(|v| v.push_str("_f3"));
+
+        let result = builder.into_value(|| "default".to_owned());
+
+        assert_eq!("[(default_f1)_f2_f3]", result);
+    }
+
+    #[test]
+    fn default_push_fluent_override() {
+        let mut builder = SharedFluentBuilder::<String>::default();
+
+        builder.push_fluent_mut(|v| v.push_str("_f1"));
+        builder.push_fluent(|v| format!("{}_f2", v));
+
+        let result = builder.into_value(|| "default".to_owned());
+
+        assert_eq!("default_f2", result);
+    }
+
+    #[test]
+    fn value_push_fluent_eager() {
+        let mut builder = BoxedFluentBuilder::<String, Eager>::default();
+
+        builder.set_value("value".to_owned());
+        builder.push_fluent_mut(|v| v.push_str("_f1"));
+
+        let result = builder.into_value(|| "default".to_owned());
+
+        assert_eq!("value_f1", result);
+    }
+
+    #[test]
+    fn set_value_take() {
+        let mut builder = BoxedFluentBuilder::<String, Stack>::default();
+
+        builder.push_fluent_mut(|v| v.push_str("_f1"));
+        builder.set_value("value".to_owned());
+
+        let taken = builder.take();
+
+        assert_eq!("value", taken.into_value(|| "default".to_owned()));
+        assert_eq!("default", builder.into_value(|| "default".to_owned()));
+    }
+}
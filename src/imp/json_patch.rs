@@ -0,0 +1,366 @@
+/*!
+Fluent methods that apply JSON merge patches.
+
+A merge patch is applied by serializing the value into JSON, merging the patch into it following
+[RFC 7386](https://tools.ietf.org/html/rfc7386), and then deserializing the result.
+Either of those steps can fail, so patches are context-aware fluent methods that record their errors in a `PatchContext`.
+Builders with patches can only be converted using `into_value_patched`, which returns the first error that was encountered.
+*/
+
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
+use serde_json::{self, Map, Value};
+
+use super::context::ByValueCtx;
+use super::{Apply, FluentBuilder, Kept, Method, StackMode, Storage};
+
+/**
+The context given to JSON merge patches while a value is built.
+
+Each call to `into_value_patched` uses its own context, so errors from nested builders aren't mixed up.
+*/
+#[derive(Debug)]
+pub struct PatchContext {
+    error: RefCell<Option<PatchError>>,
+}
+
+/**
+An error applying a JSON merge patch.
+*/
+#[derive(Debug)]
+pub struct PatchError {
+    error: serde_json::Error,
+}
+
+impl PatchContext {
+    fn new() -> Self {
+        PatchContext {
+            error: RefCell::new(None),
+        }
+    }
+
+    fn apply_patch<TValue>(&self, value: TValue, patch: &Value) -> TValue
+    where
+        TValue: Serialize + DeserializeOwned,
+    {
+        let patched = serde_json::to_value(&value).and_then(|mut target| {
+            merge_patch(&mut target, patch);
+            serde_json::from_value(target)
+        });
+
+        match patched {
+            Ok(patched) => patched,
+            Err(error) => {
+                let mut first = self.error.borrow_mut();
+
+                if first.is_none() {
+                    *first = Some(PatchError { error });
+                }
+
+                value
+            }
+        }
+    }
+
+    fn into_result<TValue>(self, value: TValue) -> Result<TValue, PatchError> {
+        match self.error.into_inner() {
+            Some(err) => Err(err),
+            None => Ok(value),
+        }
+    }
+}
+
+impl PatchError {
+    /**
+    Get the underlying JSON error.
+    */
+    pub fn into_inner(self) -> serde_json::Error {
+        self.error
+    }
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "failed to apply a JSON merge patch: {}", self.error)
+    }
+}
+
+impl Error for PatchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl<TValue, TStack, TStorage, TContext: ?Sized> FluentBuilder<TValue, TStack, TStorage, TContext>
+where
+    TStack: StackMode,
+    TValue: Serialize + DeserializeOwned,
+    TStorage: Storage<TValue, TContext>,
+{
+    /**
+    Add a JSON merge patch to the builder.
+
+    This method behaves the same as `fluent_ctx`, but applies the patch instead of a closure.
+    The builder can then only be converted using `into_value_patched`, so errors can be handled.
+    Any context-aware fluent methods already on the builder need to accept a `PatchContext`.
+
+    # Examples
+
+    ```
+    # extern crate serde_json;
+    # #[macro_use] extern crate serde;
+    # extern crate fluent_builder;
+    # use fluent_builder::{FluentBuilder, Stack};
+    # fn main() {
+    #[derive(Serialize, Deserialize)]
+    struct Config {
+        host: String,
+        port: u16,
+    }
+
+    let config = FluentBuilder::<Config, Stack>::new()
+        .fluent_patch(serde_json::json!({ "port": 8080 }))
+        .into_value_patched(|| Config {
+            host: "localhost".to_owned(),
+            port: 80,
+        })
+        .unwrap();
+
+    assert_eq!("localhost", config.host);
+    assert_eq!(8080, config.port);
+    # }
+    ```
+
+    A builder with patches can't be converted using `into_value`:
+
+    ```compile_fail
+    # extern crate serde_json;
+    # extern crate fluent_builder;
+    # use fluent_builder::{FluentBuilder, Stack};
+    # fn main() {
+    let value = FluentBuilder::<u16, Stack>::new()
+        .fluent_patch(serde_json::json!(8080))
+        .into_value(|| 80);
+    # }
+    ```
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn fluent_patch(
+        self,
+        patch: Value,
+    ) -> FluentBuilder<
+        TValue,
+        TStack,
+        Apply<
+            TValue,
+            Kept<TStack, TValue, PatchContext, TStorage::Method>,
+            ByValueCtx<impl FnOnce(&PatchContext, TValue) -> TValue>,
+        >,
+        PatchContext,
+    >
+    where
+        TStorage::Method: Method<TValue, PatchContext>,
+    {
+        self.fluent_ctx(move |ctx: &PatchContext, value| ctx.apply_patch(value, &patch))
+    }
+}
+
+impl<TValue, TStack, TStorage> FluentBuilder<TValue, TStack, TStorage, PatchContext>
+where
+    TStorage: Storage<TValue, PatchContext>,
+{
+    /**
+    Convert the fluent builder into a value, returning any errors from JSON merge patches.
+
+    This method behaves the same as `into_value_with`.
+    If a patch fails to apply then the value is left unchanged by that patch, the remaining fluent methods are still applied,
+    and the first error is returned instead of the value.
+    */
+    pub fn into_value_patched<TDefault>(self, default_value: TDefault) -> Result<TValue, PatchError>
+    where
+        TDefault: FnOnce() -> TValue,
+    {
+        let ctx = PatchContext::new();
+        let value = self.into_value_with(&ctx, default_value);
+
+        ctx.into_result(value)
+    }
+}
+
+fn merge_patch(target: &mut Value, patch: &Value) {
+    match *patch {
+        Value::Object(ref patch) => {
+            if !target.is_object() {
+                *target = Value::Object(Map::new());
+            }
+
+            if let Value::Object(ref mut target) = *target {
+                for (key, value) in patch {
+                    if value.is_null() {
+                        target.remove(key);
+                    } else {
+                        merge_patch(target.entry(key.as_str()).or_insert(Value::Null), value);
+                    }
+                }
+            }
+        }
+        ref patch => *target = patch.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::merge_patch;
+    use imp::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Config {
+        host: String,
+        port: u16,
+        tls: Option<Tls>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Tls {
+        cert: String,
+        verify: bool,
+    }
+
+    fn default_config() -> Config {
+        Config {
+            host: "localhost".to_owned(),
+            port: 80,
+            tls: Some(Tls {
+                cert: "cert.pem".to_owned(),
+                verify: true,
+            }),
+        }
+    }
+
+    #[test]
+    fn merge_patch_rfc_examples() {
+        let cases = vec![
+            (json!({"a": "b"}), json!({"a": "c"}), json!({"a": "c"})),
+            (
+                json!({"a": "b"}),
+                json!({"b": "c"}),
+                json!({"a": "b", "b": "c"}),
+            ),
+            (json!({"a": "b"}), json!({"a": null}), json!({})),
+            (
+                json!({"a": "b", "b": "c"}),
+                json!({"a": null}),
+                json!({"b": "c"}),
+            ),
+            (json!({"a": ["b"]}), json!({"a": "c"}), json!({"a": "c"})),
+            (json!({"a": "c"}), json!({"a": ["b"]}), json!({"a": ["b"]})),
+            (
+                json!({"a": {"b": "c"}}),
+                json!({"a": {"b": "d", "c": null}}),
+                json!({"a": {"b": "d"}}),
+            ),
+            (
+                json!({"a": [{"b": "c"}]}),
+                json!({"a": [1]}),
+                json!({"a": [1]}),
+            ),
+            (json!(["a", "b"]), json!(["c", "d"]), json!(["c", "d"])),
+            (json!({"a": "b"}), json!(["c"]), json!(["c"])),
+            (json!({"a": "foo"}), json!(null), json!(null)),
+            (json!({"a": "foo"}), json!("bar"), json!("bar")),
+            (
+                json!({"e": null}),
+                json!({"a": 1}),
+                json!({"e": null, "a": 1}),
+            ),
+            (
+                json!([1, 2]),
+                json!({"a": "b", "c": null}),
+                json!({"a": "b"}),
+            ),
+            (
+                json!({}),
+                json!({"a": {"bb": {"ccc": null}}}),
+                json!({"a": {"bb": {}}}),
+            ),
+        ];
+
+        for (mut target, patch, expected) in cases {
+            merge_patch(&mut target, &patch);
+
+            assert_eq!(expected, target);
+        }
+    }
+
+    #[test]
+    fn default_fluent_patch_stack() {
+        let builder = FluentBuilder::<Config, Stack>::default()
+            .fluent_patch(json!({ "port": 8080 }))
+            .fluent_mut(|c| c.host.push_str(".local"))
+            .fluent_patch(json!({ "tls": { "verify": false } }));
+
+        let result = builder.into_value_patched(default_config).unwrap();
+
+        let expected = Config {
+            host: "localhost.local".to_owned(),
+            port: 8080,
+            tls: Some(Tls {
+                cert: "cert.pem".to_owned(),
+                verify: false,
+            }),
+        };
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn default_fluent_patch_override() {
+        let builder = FluentBuilder::<Config>::default()
+            .fluent_patch(json!({ "port": 8080 }))
+            .fluent_patch(json!({ "tls": null }));
+
+        let result = builder.into_value_patched(default_config).unwrap();
+
+        let expected = Config {
+            host: "localhost".to_owned(),
+            port: 80,
+            tls: None,
+        };
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn default_fluent_patch_err() {
+        let builder = FluentBuilder::<Config, Stack>::default()
+            .fluent_patch(json!({ "port": "not a port" }))
+            .fluent_patch(json!({ "host": "example.com" }));
+
+        assert!(builder.into_value_patched(default_config).is_err());
+    }
+
+    #[test]
+    fn nested_fluent_patch_err() {
+        let builder = FluentBuilder::<Config, Stack>::default()
+            .fluent_mut(|c| {
+                let port = FluentBuilder::<u16, Stack>::default()
+                    .fluent_patch(json!("not a port"))
+                    .into_value_patched(|| 443);
+
+                assert!(port.is_err());
+                c.port = port.unwrap_or(8080);
+            })
+            .fluent_patch(json!({ "tls": null }));
+
+        let result = builder.into_value_patched(default_config).unwrap();
+
+        assert_eq!(8080, result.port);
+        assert_eq!(None, result.tls);
+    }
+}
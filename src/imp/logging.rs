@@ -0,0 +1,171 @@
+/*!
+Debug events for fluent methods as they're applied.
+*/
+
+use std::time::Instant;
+
+use log::Level;
+
+use super::{Base, Step, Steps};
+
+pub(super) const TARGET: &str = "fluent_builder";
+
+/**
+Whether debug events for fluent methods will be emitted.
+*/
+pub(super) fn enabled() -> bool {
+    log_enabled!(target: TARGET, Level::Debug)
+}
+
+/**
+Apply each step in a fluent method, emitting a debug event after each one.
+*/
+pub(super) struct LogSteps {
+    base: Base,
+    index: usize,
+}
+
+impl LogSteps {
+    pub(super) fn new(base: Base) -> Self {
+        LogSteps { base, index: 0 }
+    }
+}
+
+impl<TValue> Steps<TValue> for LogSteps {
+    fn step(
+        &mut self,
+        step: Step,
+        value: TValue,
+        apply: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        let index = self.index;
+        self.index += 1;
+
+        let start = Instant::now();
+        let value = apply(value, self);
+
+        debug!(
+            target: TARGET,
+            "applied fluent method {} `{}` over the {} in {:?}",
+            index,
+            step,
+            self.base,
+            start.elapsed()
+        );
+
+        value
+    }
+}
+
+/**
+A logger that captures events for the current thread, for tests.
+*/
+#[cfg(test)]
+pub(super) mod capture {
+    use std::cell::RefCell;
+    use std::sync::Once;
+
+    use log::{self, LevelFilter, Log, Metadata, Record};
+
+    use super::TARGET;
+
+    struct CaptureLogger;
+
+    thread_local! {
+        static CAPTURED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    impl Log for CaptureLogger {
+        fn enabled(&self, metadata: &Metadata) -> bool {
+            metadata.target() == TARGET
+        }
+
+        fn log(&self, record: &Record) {
+            if self.enabled(record.metadata()) {
+                CAPTURED.with(|captured| captured.borrow_mut().push(record.args().to_string()));
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CaptureLogger = CaptureLogger;
+    static INIT: Once = Once::new();
+
+    pub(in imp) fn capture<TCapture>(f: TCapture) -> Vec<String>
+    where
+        TCapture: FnOnce(),
+    {
+        INIT.call_once(|| {
+            log::set_logger(&LOGGER).expect("failed to set logger");
+            log::set_max_level(LevelFilter::Debug);
+        });
+
+        f();
+
+        CAPTURED.with(|captured| captured.borrow_mut().drain(..).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::capture::capture;
+    use imp::*;
+
+    #[test]
+    fn default_fluent_stack_logs_steps() {
+        let events = capture(|| {
+            let result = FluentBuilder::<String, Stack>::default()
+                .fluent_mut(|v| v.push_str("_f1"))
+                .label("push f1")
+                .fluent(|v| format!("{}_f2", v))
+                .into_value(|| "default".to_owned());
+
+            assert_eq!("default_f1_f2", result);
+        });
+
+        assert_eq!(2, events.len());
+        assert!(
+            events[0].starts_with("applied fluent method 0 `push f1` over the default value in ")
+        );
+        assert!(events[1].starts_with(&format!("applied fluent method 1 `{}:", file!())));
+        assert!(events[1].contains("` over the default value in "));
+    }
+
+    #[test]
+    fn value_fluent_override_logs_steps() {
+        let events = capture(|| {
+            let result = FluentBuilder::<String>::default()
+                .value("value".to_owned())
+                .fluent_mut(|v| v.push_str("_f1"))
+                .label("push f1")
+                .boxed()
+                .into_value(|| "default".to_owned());
+
+            assert_eq!("default_f1", result);
+        });
+
+        assert_eq!(1, events.len());
+        assert!(
+            events[0].starts_with("applied fluent method 0 `push f1` over the default value in ")
+        );
+    }
+
+    #[test]
+    fn stateful_value_fluent_stack_logs_steps() {
+        let events = capture(|| {
+            let result =
+                StatefulFluentBuilder::<i32, String, Stack>::from_value("value".to_owned())
+                    .fluent_mut(1, |i, v| v.push_str(&i.to_string()))
+                    .label("push seed")
+                    .into_value(|i| i.to_string());
+
+            assert_eq!("value1", result);
+        });
+
+        assert_eq!(1, events.len());
+        assert!(
+            events[0].starts_with("applied fluent method 0 `push seed` over the builder value in ")
+        );
+    }
+}
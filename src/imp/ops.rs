@@ -0,0 +1,380 @@
+/*!
+Fluent operations that are plain values instead of closures.
+*/
+
+use std::mem;
+
+use super::{
+    FluentBuilder, Method, Override, Replay, Stack, State, StatefulFluentBuilder,
+    StatefulFluentBuilderInner, Step, Steps, Storage,
+};
+
+/**
+A fluent operation that can be applied to a value.
+
+Unlike closures, operations are plain values.
+That means builders that store them can be printed, compared, cloned and serialized
+as long as the operations themselves can be.
+*/
+pub trait Op<TValue> {
+    /**
+    Apply the operation to a value.
+    */
+    fn apply(self, value: TValue) -> TValue;
+}
+
+/**
+Fluent operations will be stored in a list.
+
+This is a storage mode for builders, like `Inline` or `Boxed`.
+Operations are added using the `op` method on a builder, and are applied in the order they were added.
+Closures can still be given to the builder using `fluent` or `fluent_mut`, and will be applied after operations.
+
+# Examples
+
+```
+use fluent_builder::{Op, FluentBuilder, Ops, Stack};
+
+#[derive(Debug, Clone, PartialEq)]
+enum StringOp {
+    Push(&'static str),
+    Clear,
+}
+
+impl Op<String> for StringOp {
+    fn apply(self, mut value: String) -> String {
+        match self {
+            StringOp::Push(s) => value.push_str(s),
+            StringOp::Clear => value.clear(),
+        }
+
+        value
+    }
+}
+
+let builder = FluentBuilder::<String, Stack, Ops<StringOp>>::default()
+    .op(StringOp::Clear)
+    .op(StringOp::Push("A value"));
+
+assert_eq!(&[StringOp::Clear, StringOp::Push("A value")], builder.ops());
+
+// The same operations can be replayed over different defaults
+let value1 = builder.clone().into_value(|| "A default value".to_owned());
+let value2 = builder.into_value(|| "Another default value".to_owned());
+
+assert_eq!(value1, value2);
+```
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ops<TOp> {
+    ops: Vec<TOp>,
+}
+
+impl<TOp> Default for Ops<TOp> {
+    fn default() -> Self {
+        Ops { ops: Vec::new() }
+    }
+}
+
+impl<TOp> Ops<TOp> {
+    pub(super) fn from_vec(ops: Vec<TOp>) -> Self {
+        Ops { ops }
+    }
+
+    pub(super) fn as_slice(&self) -> &[TOp] {
+        &self.ops
+    }
+}
+
+impl<TValue, TContext: ?Sized, TOp> Method<TValue, TContext> for Ops<TOp>
+where
+    TOp: Op<TValue>,
+{
+    fn apply(&mut self, _: &TContext, value: TValue) -> TValue {
+        mem::take(&mut self.ops)
+            .into_iter()
+            .fold(value, |value, op| op.apply(value))
+    }
+
+    fn apply_steps(
+        &mut self,
+        _: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+    ) -> TValue {
+        mem::take(&mut self.ops)
+            .into_iter()
+            .fold(value, |value, op| {
+                let mut op = Some(op);
+                steps.step(Step::unknown(), value, &mut |value, _| {
+                    op.take().expect("attempted to re-use builder").apply(value)
+                })
+            })
+    }
+}
+
+impl<TValue, TContext: ?Sized, TOp> Replay<TValue, TContext> for Ops<TOp>
+where
+    TOp: Op<TValue> + Clone,
+{
+    fn replay_then(
+        &self,
+        _: &TContext,
+        value: TValue,
+        steps: &mut dyn Steps<TValue>,
+        then: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        // Each operation is applied by value, so it's cloned out of the shared list as it's replayed
+        let value = self.ops.iter().fold(value, |value, op| {
+            steps.step(Step::unknown(), value, &mut |value, _| {
+                op.clone().apply(value)
+            })
+        });
+
+        then(value, steps)
+    }
+}
+
+impl<TValue, TContext: ?Sized, TOp> Storage<TValue, TContext> for Ops<TOp>
+where
+    TOp: Op<TValue>,
+{
+    type Method = Self;
+}
+
+impl<TValue, TStack, TOp> FluentBuilder<TValue, TStack, Ops<TOp>>
+where
+    TOp: Op<TValue>,
+{
+    /**
+    Get the operations stored in the builder.
+    */
+    pub fn ops(&self) -> &[TOp] {
+        self.inner.ops()
+    }
+}
+
+impl<TValue, TOp> FluentBuilder<TValue, Stack, Ops<TOp>>
+where
+    TOp: Op<TValue>,
+{
+    /**
+    Stack an operation on the builder.
+
+    This has the same behaviour as `fluent`, but the type of the builder doesn't change.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    pub fn op(self, op: TOp) -> Self {
+        FluentBuilder {
+            inner: self.inner.op(op),
+        }
+    }
+}
+
+impl<TValue, TOp> FluentBuilder<TValue, Override, Ops<TOp>>
+where
+    TOp: Op<TValue>,
+{
+    /**
+    Set the operation on the builder.
+
+    This has the same behaviour as `fluent`, but the type of the builder doesn't change.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    pub fn op(self, op: TOp) -> Self {
+        FluentBuilder {
+            inner: self.inner.op((), op),
+        }
+    }
+}
+
+impl<TSeed, TValue, TStack, TOp> StatefulFluentBuilder<TSeed, TValue, TStack, Ops<TOp>>
+where
+    TOp: Op<TValue>,
+{
+    /**
+    Get the operations stored in the builder.
+    */
+    pub fn ops(&self) -> &[TOp] {
+        match self.as_inner().fluent_method {
+            Some(ref ops) => ops.as_slice(),
+            None => &[],
+        }
+    }
+}
+
+impl<TSeed, TValue, TOp> StatefulFluentBuilder<TSeed, TValue, Stack, Ops<TOp>>
+where
+    TOp: Op<TValue>,
+{
+    /**
+    Stack an operation on the builder.
+
+    This has the same behaviour as `fluent`, but the type of the builder doesn't change.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    pub fn op(mut self, op: TOp) -> Self {
+        self.as_inner_mut()
+            .fluent_method
+            .get_or_insert_with(Ops::default)
+            .ops
+            .push(op);
+
+        self
+    }
+}
+
+impl<TSeed, TValue, TOp> StatefulFluentBuilder<TSeed, TValue, Override, Ops<TOp>>
+where
+    TOp: Op<TValue>,
+{
+    /**
+    Set the operation on the builder.
+
+    This has the same behaviour as `fluent`, but the type of the builder doesn't change.
+    */
+    #[must_use = "fluent methods return a new builder instead of changing the original"]
+    pub fn op(self, seed: TSeed, op: TOp) -> Self {
+        self.discard();
+
+        StatefulFluentBuilder::new(StatefulFluentBuilderInner {
+            state: State::Seed(seed),
+            fluent_method: Some(Ops::from_vec(vec![op])),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use imp::ops::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum StringOp {
+        Push(&'static str),
+    }
+
+    impl Op<String> for StringOp {
+        fn apply(self, mut value: String) -> String {
+            match self {
+                StringOp::Push(s) => value.push_str(s),
+            }
+
+            value
+        }
+    }
+
+    mod fluent_override {
+        use super::*;
+
+        #[test]
+        fn default_value_op() {
+            let builder = FluentBuilder::<String, Override, Ops<StringOp>>::default()
+                .value("value".to_owned())
+                .op(StringOp::Push("_f1"))
+                .op(StringOp::Push("_f2"));
+
+            assert_eq!(&[StringOp::Push("_f2")], builder.ops());
+
+            let result = builder.into_value(|| "default".to_owned());
+
+            assert_eq!("default_f2", result);
+        }
+    }
+
+    mod fluent_stack {
+        use super::*;
+
+        #[test]
+        fn default_value_op() {
+            let builder = FluentBuilder::<String, Stack, Ops<StringOp>>::default()
+                .value("value".to_owned())
+                .op(StringOp::Push("_f1"))
+                .op(StringOp::Push("_f2"));
+
+            assert_eq!(
+                &[StringOp::Push("_f1"), StringOp::Push("_f2")],
+                builder.ops()
+            );
+
+            let result = builder.into_value(|| "default".to_owned());
+
+            assert_eq!("value_f1_f2", result);
+        }
+
+        #[test]
+        fn default_op_fluent() {
+            let builder = FluentBuilder::<String, Stack, Ops<StringOp>>::default()
+                .op(StringOp::Push("_f1"))
+                .fluent_mut(|v| v.push_str("_f2"));
+
+            let result = builder.into_value(|| "default".to_owned());
+
+            assert_eq!("default_f1_f2", result);
+        }
+
+        #[test]
+        fn default_op_clone_eq() {
+            let builder =
+                FluentBuilder::<String, Stack, Ops<StringOp>>::default().op(StringOp::Push("_f1"));
+
+            let cloned = builder.clone();
+
+            assert_eq!(builder, cloned);
+            assert_eq!(
+                builder.into_value(|| "default".to_owned()),
+                cloned.into_value(|| "default".to_owned())
+            );
+        }
+
+        #[test]
+        fn default_op_debug() {
+            let builder =
+                FluentBuilder::<String, Stack, Ops<StringOp>>::default().op(StringOp::Push("_f1"));
+
+            assert_eq!(
+                r#"FluentBuilder { state: Seed(()), fluent_method: Some(Ops { ops: [Push("_f1")] }) }"#,
+                format!("{:?}", builder)
+            );
+            builder.discard();
+        }
+
+        #[test]
+        fn stateful_from_seed_op() {
+            let builder =
+                StatefulFluentBuilder::<&str, String, Stack, Ops<StringOp>>::from_seed("seed")
+                    .op(StringOp::Push("_f1"))
+                    .op(StringOp::Push("_f2"));
+
+            let result = builder.into_value(|seed| seed.to_owned());
+
+            assert_eq!("seed_f1_f2", result);
+        }
+
+        #[test]
+        fn stateful_build_many_op() {
+            let builder = StatefulFluentBuilder::<&str, String, Stack, Ops<StringOp>>::from_value(
+                "value".to_owned(),
+            )
+            .op(StringOp::Push("_f1"))
+            .op(StringOp::Push("_f2"));
+
+            let results: Vec<_> = builder
+                .build_many(vec!["seed1", "seed2"], |seed| seed.to_owned())
+                .collect();
+
+            assert_eq!(vec!["value_f1_f2", "value_f1_f2"], results);
+        }
+
+        #[test]
+        fn stateful_build_many_empty() {
+            let builder =
+                StatefulFluentBuilder::<&str, String, Stack, Ops<StringOp>>::from_seed("seed");
+
+            let results: Vec<_> = builder
+                .build_many(vec!["seed1", "seed2"], |seed| seed.to_owned())
+                .collect();
+
+            assert_eq!(vec!["seed1", "seed2"], results);
+        }
+    }
+}
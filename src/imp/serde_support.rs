@@ -0,0 +1,376 @@
+/*!
+Serialization for the state of fluent builders.
+
+Only the value or seed of a builder can be serialized, along with any fluent methods that are plain values, like `Ops`.
+Other fluent methods are arbitrary closures, so builders that contain them will fail to serialize.
+*/
+
+use serde::de::{Deserialize, Deserializer, Error as DeError};
+use serde::ser::{Error as SerError, Serialize, SerializeStruct, Serializer};
+
+use super::ops::Ops;
+use super::{
+    Apply, BoxedMethod, FluentBuilder, Inline, SharedMethod, State, StatefulApply,
+    StatefulFluentBuilder, StatefulFluentBuilderInner, Storage,
+};
+
+/**
+A fluent method that may be serialized.
+*/
+pub trait SerializeMethod {
+    fn serialize_method<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+}
+
+/**
+A fluent method that may be deserialized.
+*/
+pub trait DeserializeMethod<'de>: Sized {
+    fn deserialize_method<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+struct SerializeMethodField<'a, TMethod: 'a>(&'a TMethod);
+
+impl<'a, TMethod> Serialize for SerializeMethodField<'a, TMethod>
+where
+    TMethod: SerializeMethod,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize_method(serializer)
+    }
+}
+
+fn deserialize_method_field<'de, D, TMethod>(deserializer: D) -> Result<Option<TMethod>, D::Error>
+where
+    D: Deserializer<'de>,
+    TMethod: DeserializeMethod<'de>,
+{
+    TMethod::deserialize_method(deserializer).map(Some)
+}
+
+#[derive(Deserialize)]
+#[serde(
+    rename = "FluentBuilder",
+    bound(
+        deserialize = "TSeed: Deserialize<'de>, TValue: Deserialize<'de>, TMethod: DeserializeMethod<'de>"
+    )
+)]
+struct DeserializeBuilder<TSeed, TValue, TMethod> {
+    state: State<TSeed, TValue>,
+    #[serde(default, deserialize_with = "deserialize_method_field")]
+    fluent_method: Option<TMethod>,
+}
+
+impl<TSeed, TValue, TStack, TStorage> Serialize
+    for StatefulFluentBuilder<TSeed, TValue, TStack, TStorage>
+where
+    TSeed: Serialize,
+    TValue: Serialize,
+    TStorage: Storage<TValue>,
+    TStorage::Method: SerializeMethod,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let len = if self.as_inner().fluent_method.is_some() {
+            2
+        } else {
+            1
+        };
+        let mut builder = serializer.serialize_struct("FluentBuilder", len)?;
+
+        builder.serialize_field("state", &self.as_inner().state)?;
+
+        match self.as_inner().fluent_method {
+            Some(ref fluent_method) => {
+                builder.serialize_field("fluent_method", &SerializeMethodField(fluent_method))?
+            }
+            None => builder.skip_field("fluent_method")?,
+        }
+
+        builder.end()
+    }
+}
+
+impl<'de, TSeed, TValue, TStack, TStorage> Deserialize<'de>
+    for StatefulFluentBuilder<TSeed, TValue, TStack, TStorage>
+where
+    TSeed: Deserialize<'de>,
+    TValue: Deserialize<'de>,
+    TStorage: Storage<TValue>,
+    TStorage::Method: DeserializeMethod<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let DeserializeBuilder {
+            state,
+            fluent_method,
+        } = DeserializeBuilder::deserialize(deserializer)?;
+
+        Ok(StatefulFluentBuilder::new(StatefulFluentBuilderInner {
+            state,
+            fluent_method,
+        }))
+    }
+}
+
+impl<TValue, TStack, TStorage> Serialize for FluentBuilder<TValue, TStack, TStorage>
+where
+    TValue: Serialize,
+    TStorage: Storage<TValue>,
+    TStorage::Method: SerializeMethod,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.inner.serialize(serializer)
+    }
+}
+
+impl<'de, TValue, TStack, TStorage> Deserialize<'de> for FluentBuilder<TValue, TStack, TStorage>
+where
+    TValue: Deserialize<'de>,
+    TStorage: Storage<TValue>,
+    TStorage::Method: DeserializeMethod<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(FluentBuilder {
+            inner: StatefulFluentBuilder::deserialize(deserializer)?,
+        })
+    }
+}
+
+impl<TOp> SerializeMethod for Ops<TOp>
+where
+    TOp: Serialize,
+{
+    fn serialize_method<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+impl<'de, TOp> DeserializeMethod<'de> for Ops<TOp>
+where
+    TOp: Deserialize<'de>,
+{
+    fn deserialize_method<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Vec::deserialize(deserializer).map(Ops::from_vec)
+    }
+}
+
+/*
+Closures can't be serialized or deserialized.
+*/
+
+fn unsupported_serialize<S>() -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    Err(S::Error::custom(
+        "the builder contains fluent methods, which can't be serialized",
+    ))
+}
+
+fn unsupported_deserialize<'de, D, T>() -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Err(D::Error::custom(
+        "the builder contains fluent methods, which can't be deserialized",
+    ))
+}
+
+macro_rules! unsupported_method {
+    ($(impl<$($param:ident),*> for $ty:ty;)*) => {
+        $(
+            impl<$($param),*> SerializeMethod for $ty {
+                fn serialize_method<S>(&self, _: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    unsupported_serialize::<S>()
+                }
+            }
+
+            impl<'de, $($param),*> DeserializeMethod<'de> for $ty {
+                fn deserialize_method<D>(_: D) -> Result<Self, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    unsupported_deserialize::<D, Self>()
+                }
+            }
+        )*
+    };
+}
+
+unsupported_method! {
+    impl<> for Inline;
+    impl<TValue> for BoxedMethod<TValue>;
+    impl<TValue> for SharedMethod<TValue>;
+    impl<TValue, TPreviousMethod, TNextMethod> for Apply<TValue, TPreviousMethod, TNextMethod>;
+    impl<TSeed, TValue, TPreviousMethod, TNextMethod> for StatefulApply<TSeed, TValue, TPreviousMethod, TNextMethod>;
+}
+
+#[cfg(test)]
+mod tests {
+    mod stateless {
+        use imp::*;
+        use serde_json;
+
+        #[test]
+        fn default_roundtrip() {
+            let builder = FluentBuilder::<String>::default();
+
+            let json = serde_json::to_string(&builder).unwrap();
+            assert_eq!(r#"{"state":{"Seed":null}}"#, json);
+
+            let builder: FluentBuilder<String> = serde_json::from_str(&json).unwrap();
+            let result = builder.into_value(|| "default".to_owned());
+
+            assert_eq!("default", result);
+        }
+
+        #[test]
+        fn value_roundtrip() {
+            let builder = FluentBuilder::<String>::default().value("value".to_owned());
+
+            let json = serde_json::to_string(&builder).unwrap();
+            assert_eq!(r#"{"state":{"Value":"value"}}"#, json);
+            builder.discard();
+
+            let builder: FluentBuilder<String, Stack> = serde_json::from_str(&json).unwrap();
+            let result = builder
+                .fluent_mut(|v| v.push_str("_f1"))
+                .into_value(|| "default".to_owned());
+
+            assert_eq!("value_f1", result);
+        }
+
+        #[test]
+        fn fluent_err() {
+            let builder = FluentBuilder::<String>::default()
+                .fluent_mut(|v| v.push_str("_f1"))
+                .boxed();
+
+            assert!(serde_json::to_string(&builder).is_err());
+            builder.discard();
+        }
+    }
+
+    mod stateful {
+        use imp::*;
+        use serde_json;
+
+        #[test]
+        fn seed_roundtrip() {
+            let builder = StatefulFluentBuilder::<String, String>::from_seed("seed".to_owned());
+
+            let json = serde_json::to_string(&builder).unwrap();
+            assert_eq!(r#"{"state":{"Seed":"seed"}}"#, json);
+
+            let builder: SharedStatefulFluentBuilder<String, String> =
+                serde_json::from_str(&json).unwrap();
+            let result = builder.into_value(|seed| format!("{}_default", seed));
+
+            assert_eq!("seed_default", result);
+        }
+
+        #[test]
+        fn value_roundtrip() {
+            let builder = StatefulFluentBuilder::<String, String>::from_value("value".to_owned());
+
+            let json = serde_json::to_string(&builder).unwrap();
+            assert_eq!(r#"{"state":{"Value":"value"}}"#, json);
+            builder.discard();
+
+            let builder: StatefulFluentBuilder<String, String> =
+                serde_json::from_str(&json).unwrap();
+            let result = builder.into_value(|seed| format!("{}_default", seed));
+
+            assert_eq!("value", result);
+        }
+
+        #[test]
+        fn fluent_err() {
+            let builder =
+                StatefulFluentBuilder::<String, String, Stack>::from_seed("seed".to_owned())
+                    .fluent_mut("f1".to_owned(), |s, v| v.push_str(&s));
+
+            let err = serde_json::to_string(&builder).unwrap_err();
+
+            assert!(err.to_string().contains("fluent methods"));
+            builder.discard();
+        }
+    }
+
+    mod ops {
+        use imp::*;
+        use serde_json;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        enum StringOp {
+            Push(String),
+        }
+
+        impl ops::Op<String> for StringOp {
+            fn apply(self, mut value: String) -> String {
+                match self {
+                    StringOp::Push(s) => value.push_str(&s),
+                }
+
+                value
+            }
+        }
+
+        #[test]
+        fn value_op_roundtrip() {
+            let builder = FluentBuilder::<String, Stack, ops::Ops<StringOp>>::default()
+                .value("value".to_owned())
+                .op(StringOp::Push("_f1".to_owned()));
+
+            let json = serde_json::to_string(&builder).unwrap();
+            assert_eq!(
+                r#"{"state":{"Value":"value"},"fluent_method":[{"Push":"_f1"}]}"#,
+                json
+            );
+
+            let de: FluentBuilder<String, Stack, ops::Ops<StringOp>> =
+                serde_json::from_str(&json).unwrap();
+            assert_eq!(builder, de);
+            builder.discard();
+
+            let result = de.into_value(|| "default".to_owned());
+
+            assert_eq!("value_f1", result);
+        }
+
+        #[test]
+        fn fluent_method_err() {
+            let json = r#"{"state":{"Value":"value"},"fluent_method":[]}"#;
+
+            let de: Result<FluentBuilder<String>, _> = serde_json::from_str(json);
+
+            assert!(de.is_err());
+        }
+    }
+}
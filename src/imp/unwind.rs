@@ -0,0 +1,449 @@
+/*!
+Fluent methods that panic without unwinding through the whole builder.
+*/
+
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe, Location};
+use std::sync::{Mutex, PoisonError};
+
+use super::{FluentBuilder, Method, StatefulFluentBuilder, Step, Steps, Storage};
+
+/**
+A fluent method that panicked while it was being applied.
+
+The panic hook still runs for the original panic, so the message will usually be printed as well.
+Panics are `Send` and `Sync`, so they can be returned through error types that need to be shared between threads.
+*/
+pub struct StepPanic {
+    index: usize,
+    step: Step,
+    message: Option<String>,
+    // The payload is only `Send`, so it's kept behind a lock to make the panic `Sync`
+    payload: Mutex<Box<dyn Any + Send>>,
+}
+
+impl StepPanic {
+    fn new(index: usize, step: Step, payload: Box<dyn Any + Send>) -> Self {
+        let message = match payload.downcast_ref::<&'static str>() {
+            Some(message) => Some((*message).to_owned()),
+            None => payload.downcast_ref::<String>().cloned(),
+        };
+
+        StepPanic {
+            index,
+            step,
+            message,
+            payload: Mutex::new(payload),
+        }
+    }
+
+    /**
+    The position of the fluent method in the order they were applied, starting from `0`.
+    */
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /**
+    The label given to the fluent method, if there is one.
+    */
+    pub fn label(&self) -> Option<&'static str> {
+        self.step.label()
+    }
+
+    /**
+    Where the fluent method was added to the builder, if it's known.
+    */
+    pub fn location(&self) -> Option<&'static Location<'static>> {
+        self.step.location()
+    }
+
+    /**
+    The message the fluent method panicked with, if it's a string.
+    */
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /**
+    Get the original panic payload.
+
+    The payload can be passed to `std::panic::resume_unwind` to continue unwinding.
+    */
+    pub fn into_payload(self) -> Box<dyn Any + Send> {
+        self.payload
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl fmt::Debug for StepPanic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StepPanic")
+            .field("index", &self.index)
+            .field("label", &self.label())
+            .field("location", &self.location())
+            .field("message", &self.message())
+            .finish()
+    }
+}
+
+impl fmt::Display for StepPanic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "fluent method {} `{}` panicked", self.index, self.step)?;
+
+        if let Some(message) = self.message() {
+            write!(f, ": {}", message)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for StepPanic {}
+
+impl<TValue, TStack, TStorage> FluentBuilder<TValue, TStack, TStorage>
+where
+    TStorage: Storage<TValue>,
+{
+    /**
+    Convert the fluent builder into a value, catching a panic in any of its fluent methods.
+
+    This method behaves the same as `into_value`, but if a fluent method panics then the remaining methods are skipped
+    and the panic is returned along with the position and label of the method that caused it.
+    Panics in the default value are not caught.
+
+    # Examples
+
+    ```
+    # use fluent_builder::{FluentBuilder, Stack};
+    let result = FluentBuilder::<String, Stack>::default()
+        .fluent_mut(|s| s.push_str(" and more"))
+        .fluent_mut(|_| panic!("not today"))
+        .label("fail")
+        .into_value_catch_unwind(|| "A value".to_owned());
+
+    let panicked = result.unwrap_err();
+
+    assert_eq!(1, panicked.index());
+    assert_eq!(Some("fail"), panicked.label());
+    assert_eq!(Some("not today"), panicked.message());
+    ```
+    */
+    pub fn into_value_catch_unwind<TDefault>(
+        self,
+        default_value: TDefault,
+    ) -> Result<TValue, StepPanic>
+    where
+        TDefault: FnOnce() -> TValue,
+    {
+        self.inner.into_value_catch_unwind(move |_| default_value())
+    }
+
+    /**
+    Convert the fluent builder into a value, skipping any fluent methods that panic.
+
+    This method behaves the same as `into_value`, but the value is cloned before each fluent method is applied.
+    If a method panics then the clone is used in its place and the remaining methods are still applied.
+    Each panic is returned along with the value in the order they happened.
+
+    A method added using `fluent_around` applies the methods after it within its own step.
+    If it panics after calling `next` then the clone from before it is used,
+    so the results of every method after it are discarded too.
+    Those methods aren't returned as panics unless they panicked themselves.
+    */
+    pub fn into_value_skip_panics<TDefault>(
+        self,
+        default_value: TDefault,
+    ) -> (TValue, Vec<StepPanic>)
+    where
+        TValue: Clone,
+        TDefault: FnOnce() -> TValue,
+    {
+        self.inner.into_value_skip_panics(move |_| default_value())
+    }
+}
+
+impl<TSeed, TValue, TStack, TStorage> StatefulFluentBuilder<TSeed, TValue, TStack, TStorage>
+where
+    TStorage: Storage<TValue>,
+{
+    /**
+    Convert the fluent builder into a value, catching a panic in any of its fluent methods.
+
+    This has the same behaviour as `FluentBuilder.into_value_catch_unwind`.
+    */
+    pub fn into_value_catch_unwind<TDefault>(
+        self,
+        default_value: TDefault,
+    ) -> Result<TValue, StepPanic>
+    where
+        TDefault: FnOnce(TSeed) -> TValue,
+    {
+        let (value, _, fluent_method) = self.into_base(default_value);
+
+        let mut fluent_method = match fluent_method {
+            Some(fluent_method) => fluent_method,
+            None => return Ok(value),
+        };
+
+        let mut steps = CatchSteps {
+            index: 0,
+            panicked: None,
+        };
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            fluent_method.apply_steps(&(), value, &mut steps)
+        }));
+
+        match (result, steps.panicked) {
+            (Ok(value), _) => Ok(value),
+            (Err(_), Some(panicked)) => Err(panicked),
+            (Err(payload), None) => panic::resume_unwind(payload),
+        }
+    }
+
+    /**
+    Convert the fluent builder into a value, skipping any fluent methods that panic.
+
+    This has the same behaviour as `FluentBuilder.into_value_skip_panics`.
+    */
+    pub fn into_value_skip_panics<TDefault>(
+        self,
+        default_value: TDefault,
+    ) -> (TValue, Vec<StepPanic>)
+    where
+        TValue: Clone,
+        TDefault: FnOnce(TSeed) -> TValue,
+    {
+        let (value, _, fluent_method) = self.into_base(default_value);
+
+        let mut steps = SkipSteps {
+            index: 0,
+            panicked: Vec::new(),
+        };
+
+        let value = match fluent_method {
+            Some(mut fluent_method) => fluent_method.apply_steps(&(), value, &mut steps),
+            None => value,
+        };
+
+        (value, steps.panicked)
+    }
+}
+
+/**
+The payload used to unwind out of the remaining steps after one of them panics.
+*/
+struct Abandoned;
+
+/**
+Apply each step, stopping at the first one that panics.
+*/
+struct CatchSteps {
+    index: usize,
+    panicked: Option<StepPanic>,
+}
+
+impl<TValue> Steps<TValue> for CatchSteps {
+    fn step(
+        &mut self,
+        step: Step,
+        value: TValue,
+        apply: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        let index = self.index;
+        self.index += 1;
+
+        match panic::catch_unwind(AssertUnwindSafe(|| apply(value, self))) {
+            Ok(value) => value,
+            // A step applied within this one already panicked, so keep unwinding past it
+            Err(payload) if payload.is::<Abandoned>() => panic::resume_unwind(payload),
+            Err(payload) => {
+                self.panicked = Some(StepPanic::new(index, step, payload));
+
+                // The value was lost in the panic, so there's nothing to apply later steps to
+                panic::resume_unwind(Box::new(Abandoned))
+            }
+        }
+    }
+}
+
+/**
+Apply each step, falling back to a clone of the value for any that panic.
+*/
+struct SkipSteps {
+    index: usize,
+    panicked: Vec<StepPanic>,
+}
+
+impl<TValue> Steps<TValue> for SkipSteps
+where
+    TValue: Clone,
+{
+    fn step(
+        &mut self,
+        step: Step,
+        value: TValue,
+        apply: &mut dyn FnMut(TValue, &mut dyn Steps<TValue>) -> TValue,
+    ) -> TValue {
+        let index = self.index;
+        self.index += 1;
+
+        let fallback = value.clone();
+
+        match panic::catch_unwind(AssertUnwindSafe(|| apply(value, self))) {
+            Ok(value) => value,
+            Err(payload) => {
+                self.panicked.push(StepPanic::new(index, step, payload));
+
+                fallback
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use imp::*;
+
+    #[test]
+    fn default_fluent_catch_unwind_ok() {
+        let result = FluentBuilder::<String, Stack>::default()
+            .fluent_mut(|v| v.push_str("_f1"))
+            .fluent_mut(|v| v.push_str("_f2"))
+            .into_value_catch_unwind(|| "default".to_owned());
+
+        assert_eq!("default_f1_f2", result.unwrap());
+    }
+
+    #[test]
+    fn step_panic_send_sync() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+        let err = FluentBuilder::<String, Stack>::default()
+            .fluent_mut(|_| panic!("failed at {}", "f1".to_owned()))
+            .into_value_catch_unwind(|| "default".to_owned())
+            .unwrap_err();
+
+        assert_send_sync(&err);
+        assert_eq!(Some("failed at f1"), err.message());
+
+        let payload = err.into_payload();
+        assert_eq!(
+            Some("failed at f1"),
+            payload.downcast_ref::<String>().map(|m| &**m)
+        );
+    }
+
+    #[test]
+    fn default_fluent_catch_unwind_err() {
+        let result = FluentBuilder::<String, Stack>::default()
+            .fluent_mut(|v| v.push_str("_f1"))
+            .fluent_mut(|_| panic!("failed at f2"))
+            .fluent_mut(|_| unreachable!())
+            .into_value_catch_unwind(|| "default".to_owned());
+
+        let panicked = result.unwrap_err();
+
+        assert_eq!(1, panicked.index());
+        assert_eq!(None, panicked.label());
+        assert_eq!(Some(file!()), panicked.location().map(|l| l.file()));
+        assert_eq!(Some("failed at f2"), panicked.message());
+    }
+
+    #[test]
+    fn default_fluent_catch_unwind_around() {
+        let result = FluentBuilder::<String, Stack>::default()
+            .fluent_around(|v, next| next(v))
+            .fluent_mut(|_| panic!("failed at f1"))
+            .into_value_catch_unwind(|| "default".to_owned());
+
+        let panicked = result.unwrap_err();
+
+        assert_eq!(1, panicked.index());
+        assert_eq!(Some("failed at f1"), panicked.message());
+    }
+
+    #[test]
+    fn default_fluent_catch_unwind_boxed_label() {
+        let result = FluentBuilder::<String, Stack>::default()
+            .fluent_mut(|v| v.push_str("_f1"))
+            .boxed()
+            .fluent_mut(|_| panic!("failed at {}", "f2"))
+            .label("f2")
+            .into_value_catch_unwind(|| "default".to_owned());
+
+        let panicked = result.unwrap_err();
+
+        assert_eq!(1, panicked.index());
+        assert_eq!(Some("f2"), panicked.label());
+        assert_eq!(Some("failed at f2"), panicked.message());
+        assert_eq!(
+            "fluent method 1 `f2` panicked: failed at f2",
+            panicked.to_string()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "default failed")]
+    fn default_catch_unwind_default_panics() {
+        let _ = FluentBuilder::<String, Stack>::default()
+            .fluent_mut(|v| v.push_str("_f1"))
+            .into_value_catch_unwind(|| panic!("default failed"));
+    }
+
+    #[test]
+    fn default_fluent_skip_panics() {
+        let (result, panicked) = FluentBuilder::<String, Stack>::default()
+            .fluent_mut(|v| v.push_str("_f1"))
+            .fluent_mut(|v| {
+                v.push_str("_f2");
+                panic!("failed at f2")
+            })
+            .label("f2")
+            .fluent_mut(|v| v.push_str("_f3"))
+            .into_value_skip_panics(|| "default".to_owned());
+
+        assert_eq!("default_f1_f3", result);
+
+        assert_eq!(1, panicked.len());
+        assert_eq!(1, panicked[0].index());
+        assert_eq!(Some("f2"), panicked[0].label());
+    }
+
+    #[test]
+    fn default_fluent_skip_panics_around() {
+        let (result, panicked) = FluentBuilder::<String, Stack>::default()
+            .fluent_mut(|v| v.push_str("_f1"))
+            .fluent_around(|v, next| {
+                let _ = next(v);
+                panic!("failed at around")
+            })
+            .fluent_mut(|v| v.push_str("_f3"))
+            .fluent_mut(|v| v.push_str("_f4"))
+            .into_value_skip_panics(|| "default".to_owned());
+
+        // The methods after the around method were applied within it, so they're discarded with it
+        assert_eq!("default_f1", result);
+
+        assert_eq!(1, panicked.len());
+        assert_eq!(1, panicked[0].index());
+        assert_eq!(Some("failed at around"), panicked[0].message());
+    }
+
+    #[test]
+    fn stateful_fluent_catch_unwind_err() {
+        let result = StatefulFluentBuilder::<i32, String, Stack>::from_seed(1)
+            .fluent(2, |_, _| panic!("failed at f1"))
+            .label("f1")
+            .into_value_catch_unwind(|i| i.to_string());
+
+        let panicked = result.unwrap_err();
+
+        assert_eq!(0, panicked.index());
+        assert_eq!(Some("f1"), panicked.label());
+    }
+}
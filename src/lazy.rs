@@ -0,0 +1,331 @@
+use std::cell::{Cell, OnceCell};
+use std::fmt;
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+use {BoxedFluentBuilder, DefaultStack, SharedFluentBuilder};
+
+/**
+A value that's built from a fluent builder the first time it's accessed.
+
+The builder is converted using `into_value` with the given default at most once, and the result is cached.
+If the value is never accessed then the builder is discarded when the `LazyFluent` is dropped.
+Use `SharedLazyFluent` for a value that can be shared between threads.
+
+# Examples
+
+```
+use fluent_builder::{FluentBuilder, LazyFluent, Stack};
+
+let builder = FluentBuilder::<String, Stack>::default()
+    .fluent_mut(|s| s.push_str(" and more"))
+    .boxed();
+
+let mut lazy = LazyFluent::new(builder, || "A value".to_owned());
+
+assert_eq!("A value and more", lazy.get());
+
+lazy.get_mut().push_str(" and more again");
+
+assert_eq!("A value and more and more again", lazy.into_value());
+```
+*/
+pub struct LazyFluent<TValue, TStack = DefaultStack> {
+    value: OnceCell<TValue>,
+    init: Cell<Option<BoxedLazyInit<TValue, TStack>>>,
+}
+
+/**
+A value that's built from a shared fluent builder the first time it's accessed.
+
+This has the same behaviour as `LazyFluent`, but can be shared between threads.
+If multiple threads access the value at the same time then only one of them will build it.
+*/
+pub struct SharedLazyFluent<TValue, TStack = DefaultStack> {
+    value: OnceLock<TValue>,
+    init: Mutex<Option<SharedLazyInit<TValue, TStack>>>,
+}
+
+type BoxedLazyInit<TValue, TStack> =
+    LazyInit<BoxedFluentBuilder<TValue, TStack>, Box<dyn FnOnce() -> TValue>>;
+
+type SharedLazyInit<TValue, TStack> =
+    LazyInit<SharedFluentBuilder<TValue, TStack>, Box<dyn FnOnce() -> TValue + Send>>;
+
+struct LazyInit<TBuilder, TDefault> {
+    builder: TBuilder,
+    default_value: TDefault,
+}
+
+impl<TValue, TStack> LazyFluent<TValue, TStack> {
+    /**
+    Create a new `LazyFluent` that will build its value from the given builder and default.
+    */
+    pub fn new<TDefault>(
+        builder: BoxedFluentBuilder<TValue, TStack>,
+        default_value: TDefault,
+    ) -> Self
+    where
+        TDefault: FnOnce() -> TValue + 'static,
+    {
+        LazyFluent {
+            value: OnceCell::new(),
+            init: Cell::new(Some(LazyInit {
+                builder,
+                default_value: Box::new(default_value),
+            })),
+        }
+    }
+
+    /**
+    Get the value, building it if this is the first time it's been accessed.
+
+    If building the value panics then this method and any other that accesses the value will also panic.
+    */
+    pub fn get(&self) -> &TValue {
+        self.value.get_or_init(|| {
+            let LazyInit {
+                builder,
+                default_value,
+            } = self
+                .init
+                .take()
+                .expect("the lazy value previously failed to build");
+
+            builder.into_value(default_value)
+        })
+    }
+
+    /**
+    Get a mutable reference to the value, building it if this is the first time it's been accessed.
+    */
+    pub fn get_mut(&mut self) -> &mut TValue {
+        self.get();
+        self.value.get_mut().expect("missing lazy value")
+    }
+
+    /**
+    Whether the value has been built yet.
+    */
+    pub fn is_built(&self) -> bool {
+        self.value.get().is_some()
+    }
+
+    /**
+    Convert the `LazyFluent` into its value, building it if it hasn't been accessed yet.
+    */
+    pub fn into_value(mut self) -> TValue {
+        self.get_mut();
+        self.value.take().expect("missing lazy value")
+    }
+}
+
+impl<TValue, TStack> Drop for LazyFluent<TValue, TStack> {
+    fn drop(&mut self) {
+        if let Some(init) = self.init.take() {
+            init.builder.discard();
+        }
+    }
+}
+
+impl<TValue, TStack> fmt::Debug for LazyFluent<TValue, TStack>
+where
+    TValue: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LazyFluent")
+            .field("value", &self.value.get())
+            .finish()
+    }
+}
+
+impl<TValue, TStack> SharedLazyFluent<TValue, TStack> {
+    /**
+    Create a new `SharedLazyFluent` that will build its value from the given builder and default.
+    */
+    pub fn new<TDefault>(
+        builder: SharedFluentBuilder<TValue, TStack>,
+        default_value: TDefault,
+    ) -> Self
+    where
+        TDefault: FnOnce() -> TValue + Send + 'static,
+    {
+        SharedLazyFluent {
+            value: OnceLock::new(),
+            init: Mutex::new(Some(LazyInit {
+                builder,
+                default_value: Box::new(default_value),
+            })),
+        }
+    }
+
+    /**
+    Get the value, building it if this is the first time it's been accessed.
+
+    If building the value panics then this method and any other that accesses the value will also panic.
+    */
+    pub fn get(&self) -> &TValue {
+        self.value.get_or_init(|| {
+            let LazyInit {
+                builder,
+                default_value,
+            } = self
+                .take_init()
+                .expect("the lazy value previously failed to build");
+
+            builder.into_value(default_value)
+        })
+    }
+
+    /**
+    Get a mutable reference to the value, building it if this is the first time it's been accessed.
+    */
+    pub fn get_mut(&mut self) -> &mut TValue {
+        self.get();
+        self.value.get_mut().expect("missing lazy value")
+    }
+
+    /**
+    Whether the value has been built yet.
+    */
+    pub fn is_built(&self) -> bool {
+        self.value.get().is_some()
+    }
+
+    /**
+    Convert the `SharedLazyFluent` into its value, building it if it hasn't been accessed yet.
+    */
+    pub fn into_value(mut self) -> TValue {
+        self.get_mut();
+        self.value.take().expect("missing lazy value")
+    }
+
+    fn take_init(&self) -> Option<SharedLazyInit<TValue, TStack>> {
+        self.init
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+}
+
+impl<TValue, TStack> Drop for SharedLazyFluent<TValue, TStack> {
+    fn drop(&mut self) {
+        if let Some(init) = self.take_init() {
+            init.builder.discard();
+        }
+    }
+}
+
+impl<TValue, TStack> fmt::Debug for SharedLazyFluent<TValue, TStack>
+where
+    TValue: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SharedLazyFluent")
+            .field("value", &self.value.get())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::thread;
+
+    use lazy::*;
+    use {FluentBuilder, Stack};
+
+    #[test]
+    fn lazy_builds_once() {
+        let builds = Rc::new(Cell::new(0));
+
+        let lazy = {
+            let builds = builds.clone();
+
+            LazyFluent::new(
+                FluentBuilder::<String, Stack>::default()
+                    .fluent_mut(|v| v.push_str("_f1"))
+                    .boxed(),
+                move || {
+                    builds.set(builds.get() + 1);
+                    "default".to_owned()
+                },
+            )
+        };
+
+        assert!(!lazy.is_built());
+        assert_eq!(0, builds.get());
+
+        assert_eq!("default_f1", lazy.get());
+        assert_eq!("default_f1", lazy.get());
+
+        assert!(lazy.is_built());
+        assert_eq!(1, builds.get());
+    }
+
+    #[test]
+    fn lazy_value_get_mut() {
+        let mut lazy = LazyFluent::new(
+            FluentBuilder::<String, Stack>::default()
+                .value("value".to_owned())
+                .boxed(),
+            || "default".to_owned(),
+        );
+
+        lazy.get_mut().push_str("_f1");
+
+        assert_eq!("value_f1", lazy.into_value());
+    }
+
+    #[test]
+    fn lazy_drop_unbuilt() {
+        let lazy = LazyFluent::new(
+            FluentBuilder::<String, Stack>::default()
+                .fluent_mut(|v| v.push_str("_f1"))
+                .boxed(),
+            || "default".to_owned(),
+        );
+
+        drop(lazy);
+    }
+
+    #[test]
+    fn lazy_debug() {
+        let lazy = LazyFluent::new(FluentBuilder::<String>::default().boxed(), || {
+            "default".to_owned()
+        });
+
+        assert_eq!("LazyFluent { value: None }", format!("{:?}", lazy));
+
+        lazy.get();
+
+        assert_eq!(
+            r#"LazyFluent { value: Some("default") }"#,
+            format!("{:?}", lazy)
+        );
+    }
+
+    #[test]
+    fn shared_lazy_builds_once_across_threads() {
+        let lazy = Arc::new(SharedLazyFluent::new(
+            FluentBuilder::<String, Stack>::default()
+                .fluent_mut(|v| v.push_str("_f1"))
+                .shared(),
+            || "default".to_owned(),
+        ));
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let lazy = lazy.clone();
+                thread::spawn(move || lazy.get().clone())
+            })
+            .collect();
+
+        for handle in handles {
+            assert_eq!("default_f1", handle.join().unwrap());
+        }
+
+        assert!(lazy.is_built());
+    }
+}
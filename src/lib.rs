@@ -6,7 +6,8 @@ It offers some standard behaviour for constructing values from a given source, o
 This crate is intended to be used within other builders rather than consumed by your users directly.
 It's especially useful for managing the complexity of keeping builders ergonomic when they're nested within other builders.
 
-This crate is currently designed around builders that take self by-value instead of by-reference.
+This crate is mostly designed around builders that take self by-value instead of by-reference.
+Values with by-reference methods can still be built using `fluent_ref` or the `RefBuilder` type.
 
 # Usage
 
@@ -64,6 +65,33 @@ For collection-like values it might make more sense to use stacking builders.
 For other kinds of values it probably makes more sense to use overriding builders, so they're the default choice.
 Using a generic parameter instead of some value to control whether or not fluent methods are stacked means you can enforce a particular style through Rust's type system.
 
+Builders can also use the `Eager` stacking mode.
+Eager builders apply fluent methods to a value as soon as they're added, and only stack them while waiting on a default value:
+
+```
+use fluent_builder::{Eager, FluentBuilder};
+
+let value = FluentBuilder::<String, Eager>::default()
+    .value("A value".to_owned())
+    .fluent_mut(|s| s.push_str(" fluent1"))
+    .into_value(|| "A default value".to_owned());
+
+assert_eq!("A value fluent1", value);
+```
+
+Stacking builders can also wrap the methods added after one using `fluent_around`, to run code both before and after them:
+
+```
+use fluent_builder::{FluentBuilder, Stack};
+
+let value = FluentBuilder::<String, Stack>::default()
+    .fluent_around(|s, next| format!("{} done", next(s)))
+    .fluent_mut(|s| s.push_str(" fluent1"))
+    .into_value(|| "A default value".to_owned());
+
+assert_eq!("A default value fluent1 done", value);
+```
+
 ## Stateful builders
 
 Fluent builders can also be used to thread required state through construction:
@@ -78,7 +106,7 @@ struct Builder {
 }
 
 let value = StatefulFluentBuilder::<String, Builder>::from_seed("A required value".to_owned())
-    .fluent_mut("A required value".to_owned(), |b| {
+    .fluent_mut_with_seed(|b| {
         if let Some(ref mut optional) = b.optional.as_mut() {
             optional.push_str(" fluent1");
         }
@@ -93,7 +121,8 @@ assert_eq!("A default value fluent1", value.optional.unwrap());
 ```
 
 Stateful builders can also stack fluent methods instead of overriding them.
-The API requires each invocation of `fluent` deals with the required state:
+The API requires each invocation of `fluent` deals with the required state.
+That state can be given explicitly, or reused from the builder's current seed using `fluent_with_seed`:
 
 ```
 # #[derive(Debug, PartialEq, Eq)]
@@ -110,7 +139,7 @@ let value = StatefulFluentBuilder::<String, Builder, Stack>::from_seed("A requir
             optional.push_str(" fluent1");
         }
     })
-    .fluent_mut("A required value".to_owned(), |s, b| {
+    .fluent_mut_with_seed(|s, b| {
         b.required = s;
         if let Some(ref mut optional) = b.optional.as_mut() {
             optional.push_str(" fluent2");
@@ -125,13 +154,32 @@ assert_eq!("A required value", value.required);
 assert_eq!("A default value fluent1 fluent2", value.optional.unwrap());
 ```
 
+## By-reference builders
+
+Some values are configured using methods that take `&mut self` and return `&mut Self`.
+These methods can be stacked using `fluent_ref`:
+
+```
+use std::process::Command;
+use fluent_builder::{FluentBuilder, Stack};
+
+let cmd = FluentBuilder::<Command, Stack>::default()
+    .fluent_ref(|cmd| cmd.arg("-l"))
+    .fluent_ref(|cmd| cmd.arg("-a"))
+    .into_value(|| Command::new("ls"));
+
+assert_eq!(vec!["-l", "-a"], cmd.get_args().collect::<Vec<_>>());
+```
+
+The `RefBuilder` type also takes self by-reference itself, so it can be used in the same style as the values it builds.
+
 ## Within other builders
 
 The `FluentBuilder` and `StatefulFluentBuilder` types are designed to be used within other builders rather than directly.
 They just provide some consistent underlying behaviour with respect to assigning and mutating inner builders:
 
 ```rust
-use fluent_builder::{BoxedFluentBuilder, Stack};
+use fluent_builder::{BoxedFluentBuilder, Buildable, Stack};
 
 #[derive(Default)]
 struct RequestBuilder {
@@ -190,8 +238,9 @@ impl RequestBuilder {
     }
 
     fn build(self) -> Request {
-        // Get a `Body` by converting the `FluentBuilder` into a `BodyBuilder`
-        let body = self.body.into_value(|| BodyBuilder::default()).build();
+        // Get a `Body` by converting the `FluentBuilder` into a `BodyBuilder` and finishing it
+        // The default `BodyBuilder` comes from the `Buildable` implementation for `Body`
+        let body = self.body.build();
 
         Request {
             body: body
@@ -204,9 +253,17 @@ impl BodyBuilder {
         self.bytes.extend(bytes);
         self
     }
+}
+
+impl Buildable for Body {
+    type Builder = BodyBuilder;
+
+    fn default_builder() -> BodyBuilder {
+        BodyBuilder::default()
+    }
 
-    fn build(self) -> Body {
-        Body(self.bytes)
+    fn finish(builder: BodyBuilder) -> Body {
+        Body(builder.bytes)
     }
 }
 
@@ -226,12 +283,70 @@ assert_eq!(request1.body.0, request2.body.0);
 
 This seems like a lot of boilerplate, but comes in handy when you have a lot of potentially nested builders and need to keep them consistent.
 There's nothing really special about the above builders besides the use of `FluentBuilder`.
+
+## Serialization
+
+With the `serde` feature enabled, builders can be serialized and deserialized.
+Only the value or seed of a builder is serialized, so a deserialized builder won't contain any fluent methods.
+Attempting to serialize a builder that contains fluent methods will return an error.
+
+Fluent methods can't be serialized because they're closures.
+Builders that use `Ops` storage keep their fluent methods as plain values instead, so they can be serialized along with the value or seed.
+
+With the `json_patch` feature enabled, builders can also apply JSON merge patches using `fluent_patch`.
+
+## Logging
+
+With the `log` feature enabled, each fluent method emits a debug event with the `fluent_builder` target as it's applied.
+The event includes the method's label or caller location, whether it was applied over a value from the builder or a default, and how long it took.
+Methods can be labelled using the `label` method on a builder:
+
+```
+# use fluent_builder::{FluentBuilder, Stack};
+let value = FluentBuilder::<String, Stack>::default()
+    .fluent_mut(|s| s.push_str(" and more"))
+    .label("append more")
+    .into_value(|| "A value".to_owned());
+
+assert_eq!("A value and more", value);
+```
+
+## Checking for unused builders
+
+A builder that has collected a value or fluent methods but is never converted into a value is usually a bug.
+With the `debug-drop-check` feature enabled, dropping a builder like that will emit a warning event with the `fluent_builder` target.
+This feature also enables the `log` feature.
+Builders that are dropped deliberately can be consumed using `discard` instead.
 */
 
+#[cfg(feature = "log")]
+#[macro_use]
+extern crate log;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+
+#[cfg(any(feature = "serde_json", all(test, feature = "serde")))]
+#[cfg_attr(all(test, feature = "json_patch"), macro_use)]
+extern crate serde_json;
+
 mod imp;
+mod lazy;
+mod recorded;
+mod ref_builder;
+mod registry;
 
+pub use self::imp::ops::{Op, Ops};
 pub use self::imp::{
-    Boxed, BoxedFluentBuilder, BoxedStatefulFluentBuilder, DefaultStack, DefaultStorage,
-    FluentBuilder, Inline, Override, Shared, SharedFluentBuilder, SharedStatefulFluentBuilder,
-    Stack, StatefulFluentBuilder, TryIntoValue,
+    AnyFluentBuilder, Boxed, BoxedFluentBuilder, BoxedStatefulFluentBuilder, BuildError,
+    BuildErrors, BuildPath, BuildPathRef, Buildable, ConcurrentFluentBuilder, DefaultStack,
+    DefaultStorage, DynBuilder, Eager, FluentBuilder, Inline, NextMethod, Override, Shared,
+    SharedFluentBuilder, SharedStatefulFluentBuilder, Stack, StackMode, StatefulFluentBuilder,
+    StepPanic, TryIntoValue,
 };
+#[cfg(feature = "json_patch")]
+pub use self::imp::{PatchContext, PatchError};
+pub use self::lazy::{LazyFluent, SharedLazyFluent};
+pub use self::recorded::{Call, Recorded, Trace, TraceBase, TraceStep};
+pub use self::ref_builder::RefBuilder;
+pub use self::registry::BuilderRegistry;
@@ -0,0 +1,779 @@
+use std::fmt;
+use std::panic::Location;
+use std::sync::{Arc, Mutex};
+
+use imp::Storage;
+use {
+    BoxedFluentBuilder, BoxedStatefulFluentBuilder, FluentBuilder, Override, SharedFluentBuilder,
+    SharedStatefulFluentBuilder, Stack, StackMode, StatefulFluentBuilder,
+};
+
+/**
+A fluent builder that records the calls made to it.
+
+Each call to `fluent`, `fluent_mut` or `value` is captured along with the location it was made from,
+and an optional label given by `label`.
+Converting the builder using `into_value_traced` returns a `Trace` of the steps that actually ran,
+and the base value they were applied over.
+
+# Examples
+
+```
+use fluent_builder::{FluentBuilder, Recorded, Stack, TraceBase};
+
+let builder = Recorded::new(FluentBuilder::<String, Stack>::default())
+    .fluent_mut(|s| s.push_str(" fluent1"))
+    .label("fluent1")
+    .fluent_mut(|s| s.push_str(" fluent2"));
+
+let (value, trace) = builder.into_value_traced(|| "A default value".to_owned());
+
+assert_eq!("A default value fluent1 fluent2", value);
+
+assert_eq!(&TraceBase::Default, trace.base());
+assert_eq!(Some("fluent1"), trace.steps()[0].label());
+assert_eq!(None, trace.steps()[1].label());
+```
+*/
+#[must_use = "builders do nothing unless they're converted into a value"]
+pub struct Recorded<TBuilder> {
+    builder: TBuilder,
+    calls: Vec<TraceStep>,
+    value_call: Option<usize>,
+    ran: Arc<Mutex<Vec<usize>>>,
+}
+
+/**
+The kind of call made to a recorded builder.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Call {
+    Fluent,
+    FluentMut,
+    Value,
+}
+
+/**
+A single call made to a recorded builder.
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceStep {
+    call: Call,
+    label: Option<&'static str>,
+    location: &'static Location<'static>,
+}
+
+/**
+The base value that fluent methods were applied over.
+
+This is either a `Value` supplied to the builder by the given call,
+a default constructed from the builder's `Seed`,
+or a `Default` for builders without seeds.
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceBase {
+    Value(TraceStep),
+    Seed,
+    Default,
+}
+
+/**
+The steps that ran while converting a recorded builder into a value.
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    base: TraceBase,
+    steps: Vec<TraceStep>,
+}
+
+impl TraceStep {
+    /**
+    The kind of call that was made.
+    */
+    pub fn call(&self) -> Call {
+        self.call
+    }
+
+    /**
+    The label given to the call, if any.
+    */
+    pub fn label(&self) -> Option<&'static str> {
+        self.label
+    }
+
+    /**
+    The location the call was made from.
+    */
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+}
+
+impl Trace {
+    /**
+    The base value that fluent methods were applied over.
+    */
+    pub fn base(&self) -> &TraceBase {
+        &self.base
+    }
+
+    /**
+    The fluent methods that ran, in the order they ran in.
+    */
+    pub fn steps(&self) -> &[TraceStep] {
+        &self.steps
+    }
+}
+
+impl fmt::Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Call::Fluent => f.write_str("fluent"),
+            Call::FluentMut => f.write_str("fluent_mut"),
+            Call::Value => f.write_str("value"),
+        }
+    }
+}
+
+impl fmt::Display for TraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.call)?;
+
+        if let Some(label) = self.label {
+            write!(f, " `{}`", label)?;
+        }
+
+        write!(f, " at {}", self.location)
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.base {
+            TraceBase::Value(ref step) => write!(f, "{}", step)?,
+            TraceBase::Seed => f.write_str("default from seed")?,
+            TraceBase::Default => f.write_str("default")?,
+        }
+
+        for step in &self.steps {
+            write!(f, "\n -> {}", step)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<TBuilder> Default for Recorded<TBuilder>
+where
+    TBuilder: Default,
+{
+    fn default() -> Self {
+        Recorded::new(TBuilder::default())
+    }
+}
+
+impl<TBuilder> Recorded<TBuilder> {
+    /**
+    Start recording calls made to a builder.
+
+    Any fluent methods already in the builder will still run, but won't appear in a trace.
+    */
+    pub fn new(builder: TBuilder) -> Self {
+        Recorded {
+            builder,
+            calls: Vec::new(),
+            value_call: None,
+            ran: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /**
+    Stop recording calls and return the inner builder.
+    */
+    pub fn into_inner(self) -> TBuilder {
+        self.builder
+    }
+
+    fn label_last_call(&mut self, label: &'static str) {
+        if let Some(step) = self.calls.last_mut() {
+            step.label = Some(label);
+        }
+    }
+
+    fn map<TNextBuilder, TMap>(self, map: TMap) -> Recorded<TNextBuilder>
+    where
+        TMap: FnOnce(TBuilder) -> TNextBuilder,
+    {
+        Recorded {
+            builder: map(self.builder),
+            calls: self.calls,
+            value_call: self.value_call,
+            ran: self.ran,
+        }
+    }
+
+    fn take_builder(self) -> (TBuilder, Recorded<()>) {
+        let mut builder = None;
+        let recorded = self.map(|inner| builder = Some(inner));
+
+        (builder.expect("missing builder"), recorded)
+    }
+
+    fn record(&mut self, call: Call, location: &'static Location<'static>) -> usize {
+        let index = self.calls.len();
+        self.calls.push(TraceStep {
+            call,
+            label: None,
+            location,
+        });
+
+        index
+    }
+
+    fn record_fluent(&mut self, call: Call, location: &'static Location<'static>) -> RanMarker {
+        RanMarker {
+            index: self.record(call, location),
+            ran: self.ran.clone(),
+        }
+    }
+
+    fn into_trace(self, base: TraceBase) -> Trace {
+        let ran = self.ran.lock().unwrap_or_else(|err| err.into_inner());
+
+        Trace {
+            base,
+            steps: ran.iter().map(|index| self.calls[*index].clone()).collect(),
+        }
+    }
+
+    fn value_base(&self) -> TraceBase {
+        match self.value_call {
+            Some(index) => TraceBase::Value(self.calls[index].clone()),
+            None => TraceBase::Default,
+        }
+    }
+}
+
+/*
+Marks a recorded fluent method as having run when it's applied.
+*/
+struct RanMarker {
+    index: usize,
+    ran: Arc<Mutex<Vec<usize>>>,
+}
+
+impl RanMarker {
+    fn mark(self) {
+        self.ran
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push(self.index);
+    }
+}
+
+impl<TValue, TStack, TStorage> Recorded<FluentBuilder<TValue, TStack, TStorage>>
+where
+    TStorage: Storage<TValue>,
+{
+    /**
+    Set a value on the builder.
+
+    This has the same behaviour as `FluentBuilder.value`.
+    */
+    #[track_caller]
+    pub fn value(mut self, value: TValue) -> Self {
+        let index = self.record(Call::Value, Location::caller());
+        self.value_call = Some(index);
+
+        self.map(|builder| builder.value(value))
+    }
+
+    /**
+    Label the last call made to the builder.
+
+    Labels make steps easier to identify in a trace.
+    The label is also given to the inner builder, so it's used when the `log` feature is enabled.
+    This has the same behaviour as `FluentBuilder.label`.
+    */
+    pub fn label(mut self, label: &'static str) -> Self {
+        self.label_last_call(label);
+
+        self.map(|builder| builder.label(label))
+    }
+
+    /**
+    Convert the builder into a value, along with a trace of the steps that produced it.
+
+    This has the same behaviour as `FluentBuilder.into_value`.
+    */
+    pub fn into_value_traced<TDefault>(self, default_value: TDefault) -> (TValue, Trace)
+    where
+        TDefault: FnOnce() -> TValue,
+    {
+        let (builder, recorded) = self.take_builder();
+
+        let mut used_default = false;
+        let value = builder.into_value(|| {
+            used_default = true;
+            default_value()
+        });
+
+        let base = if used_default {
+            TraceBase::Default
+        } else {
+            recorded.value_base()
+        };
+
+        (value, recorded.into_trace(base))
+    }
+}
+
+impl<TValue, TStack, TStorage> Recorded<FluentBuilder<TValue, TStack, TStorage>>
+where
+    TValue: 'static,
+    TStorage: Storage<TValue> + 'static,
+{
+    /**
+    Box the inner builder so it can be easily captured as a field without generics.
+    */
+    pub fn boxed(self) -> Recorded<BoxedFluentBuilder<TValue, TStack>> {
+        self.map(|builder| builder.boxed())
+    }
+}
+
+impl<TValue, TStack, TStorage> Recorded<FluentBuilder<TValue, TStack, TStorage>>
+where
+    TValue: 'static,
+    TStorage: Storage<TValue>,
+    TStorage::Method: Send + 'static,
+{
+    /**
+    Box the inner builder so it can be easily shared.
+    */
+    pub fn shared(self) -> Recorded<SharedFluentBuilder<TValue, TStack>> {
+        self.map(|builder| builder.shared())
+    }
+}
+
+impl<TValue, TStack, TStorage> Recorded<FluentBuilder<TValue, TStack, TStorage>>
+where
+    TStack: StackMode,
+    TStorage: Storage<TValue>,
+{
+    /**
+    Add a fluent method to the builder.
+
+    This has the same behaviour as `FluentBuilder.fluent`.
+    */
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn fluent<TNextMethod>(
+        mut self,
+        fluent_method: TNextMethod,
+    ) -> Recorded<
+        FluentBuilder<
+            TValue,
+            TStack,
+            ::imp::Apply<
+                TValue,
+                ::imp::Kept<TStack, TValue, (), TStorage::Method>,
+                ::imp::ByValue<impl FnOnce(TValue) -> TValue>,
+            >,
+        >,
+    >
+    where
+        TNextMethod: FnOnce(TValue) -> TValue,
+    {
+        let marker = self.record_fluent(Call::Fluent, Location::caller());
+
+        let (builder, recorded) = self.take_builder();
+        let builder = builder.fluent(move |value| {
+            marker.mark();
+            fluent_method(value)
+        });
+
+        recorded.map(move |()| builder)
+    }
+
+    /**
+    Add a fluent method to the builder.
+
+    This has the same behaviour as `FluentBuilder.fluent_mut`.
+    */
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn fluent_mut<TNextMethod>(
+        mut self,
+        fluent_method: TNextMethod,
+    ) -> Recorded<
+        FluentBuilder<
+            TValue,
+            TStack,
+            ::imp::Apply<
+                TValue,
+                ::imp::Kept<TStack, TValue, (), TStorage::Method>,
+                ::imp::ByRefMut<impl FnOnce(&mut TValue)>,
+            >,
+        >,
+    >
+    where
+        TNextMethod: FnOnce(&mut TValue),
+    {
+        let marker = self.record_fluent(Call::FluentMut, Location::caller());
+
+        let (builder, recorded) = self.take_builder();
+        let builder = builder.fluent_mut(move |value| {
+            marker.mark();
+            fluent_method(value)
+        });
+
+        recorded.map(move |()| builder)
+    }
+}
+
+impl<TSeed, TValue, TStack, TStorage>
+    Recorded<StatefulFluentBuilder<TSeed, TValue, TStack, TStorage>>
+where
+    TStorage: Storage<TValue>,
+{
+    /**
+    Set a value on the builder.
+
+    This has the same behaviour as `StatefulFluentBuilder.value`.
+    */
+    #[track_caller]
+    pub fn value(mut self, value: TValue) -> Self {
+        let index = self.record(Call::Value, Location::caller());
+        self.value_call = Some(index);
+
+        self.map(|builder| builder.value(value))
+    }
+
+    /**
+    Label the last call made to the builder.
+
+    This has the same behaviour as the `FluentBuilder` recorder's `label`.
+    */
+    pub fn label(mut self, label: &'static str) -> Self {
+        self.label_last_call(label);
+
+        self.map(|builder| builder.label(label))
+    }
+
+    /**
+    Convert the builder into a value, along with a trace of the steps that produced it.
+
+    This has the same behaviour as `StatefulFluentBuilder.into_value`.
+    */
+    pub fn into_value_traced<TDefault>(self, default_value: TDefault) -> (TValue, Trace)
+    where
+        TDefault: FnOnce(TSeed) -> TValue,
+    {
+        let (builder, recorded) = self.take_builder();
+
+        let mut used_seed = false;
+        let value = builder.into_value(|seed| {
+            used_seed = true;
+            default_value(seed)
+        });
+
+        let base = if used_seed {
+            TraceBase::Seed
+        } else {
+            recorded.value_base()
+        };
+
+        (value, recorded.into_trace(base))
+    }
+}
+
+impl<TSeed, TValue, TStack, TStorage>
+    Recorded<StatefulFluentBuilder<TSeed, TValue, TStack, TStorage>>
+where
+    TSeed: 'static,
+    TStorage: Storage<TValue>,
+    TStorage::Method: 'static,
+{
+    /**
+    Box the inner builder so it can be easily captured as a field without generics.
+    */
+    pub fn boxed(self) -> Recorded<BoxedStatefulFluentBuilder<TSeed, TValue, TStack>> {
+        self.map(|builder| builder.boxed())
+    }
+}
+
+impl<TSeed, TValue, TStack, TStorage>
+    Recorded<StatefulFluentBuilder<TSeed, TValue, TStack, TStorage>>
+where
+    TSeed: 'static,
+    TStorage: Storage<TValue>,
+    TStorage::Method: Send + 'static,
+{
+    /**
+    Box the inner builder so it can be easily shared.
+    */
+    pub fn shared(self) -> Recorded<SharedStatefulFluentBuilder<TSeed, TValue, TStack>> {
+        self.map(|builder| builder.shared())
+    }
+}
+
+impl<TSeed, TValue, TStorage> Recorded<StatefulFluentBuilder<TSeed, TValue, Stack, TStorage>>
+where
+    TStorage: Storage<TValue>,
+{
+    /**
+    Stack a fluent method on the builder.
+
+    This has the same behaviour as `StatefulFluentBuilder.fluent`.
+    */
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn fluent<TNextMethod>(
+        mut self,
+        seed: TSeed,
+        fluent_method: TNextMethod,
+    ) -> Recorded<
+        StatefulFluentBuilder<
+            TSeed,
+            TValue,
+            Stack,
+            ::imp::StatefulApply<
+                TSeed,
+                TValue,
+                TStorage::Method,
+                ::imp::ByValue<impl FnOnce(TSeed, TValue) -> TValue>,
+            >,
+        >,
+    >
+    where
+        TNextMethod: FnOnce(TSeed, TValue) -> TValue,
+    {
+        let marker = self.record_fluent(Call::Fluent, Location::caller());
+
+        let (builder, recorded) = self.take_builder();
+        let builder = builder.fluent(seed, move |seed, value| {
+            marker.mark();
+            fluent_method(seed, value)
+        });
+
+        recorded.map(move |()| builder)
+    }
+
+    /**
+    Stack a fluent method on the builder.
+
+    This has the same behaviour as `StatefulFluentBuilder.fluent_mut`.
+    */
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn fluent_mut<TNextMethod>(
+        mut self,
+        seed: TSeed,
+        fluent_method: TNextMethod,
+    ) -> Recorded<
+        StatefulFluentBuilder<
+            TSeed,
+            TValue,
+            Stack,
+            ::imp::StatefulApply<
+                TSeed,
+                TValue,
+                TStorage::Method,
+                ::imp::ByRefMut<impl FnOnce(TSeed, &mut TValue)>,
+            >,
+        >,
+    >
+    where
+        TNextMethod: FnOnce(TSeed, &mut TValue),
+    {
+        let marker = self.record_fluent(Call::FluentMut, Location::caller());
+
+        let (builder, recorded) = self.take_builder();
+        let builder = builder.fluent_mut(seed, move |seed, value| {
+            marker.mark();
+            fluent_method(seed, value)
+        });
+
+        recorded.map(move |()| builder)
+    }
+}
+
+impl<TSeed, TValue, TStorage> Recorded<StatefulFluentBuilder<TSeed, TValue, Override, TStorage>>
+where
+    TStorage: Storage<TValue>,
+{
+    /**
+    Set the fluent method on the builder.
+
+    This has the same behaviour as `StatefulFluentBuilder.fluent`.
+    */
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn fluent<TNextMethod>(
+        mut self,
+        seed: TSeed,
+        fluent_method: TNextMethod,
+    ) -> Recorded<
+        StatefulFluentBuilder<
+            TSeed,
+            TValue,
+            Override,
+            ::imp::Apply<
+                TValue,
+                ::imp::Inline,
+                ::imp::ByValue<impl FnOnce(TValue) -> TValue + 'static>,
+            >,
+        >,
+    >
+    where
+        TNextMethod: FnOnce(TValue) -> TValue + 'static,
+    {
+        let marker = self.record_fluent(Call::Fluent, Location::caller());
+
+        let (builder, recorded) = self.take_builder();
+        let builder = builder.fluent(seed, move |value| {
+            marker.mark();
+            fluent_method(value)
+        });
+
+        recorded.map(move |()| builder)
+    }
+
+    /**
+    Set the fluent method on the builder.
+
+    This has the same behaviour as `StatefulFluentBuilder.fluent_mut`.
+    */
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn fluent_mut<TNextMethod>(
+        mut self,
+        seed: TSeed,
+        fluent_method: TNextMethod,
+    ) -> Recorded<
+        StatefulFluentBuilder<
+            TSeed,
+            TValue,
+            Override,
+            ::imp::Apply<
+                TValue,
+                ::imp::Inline,
+                ::imp::ByRefMut<impl FnOnce(&mut TValue) + 'static>,
+            >,
+        >,
+    >
+    where
+        TNextMethod: FnOnce(&mut TValue) + 'static,
+    {
+        let marker = self.record_fluent(Call::FluentMut, Location::caller());
+
+        let (builder, recorded) = self.take_builder();
+        let builder = builder.fluent_mut(seed, move |value| {
+            marker.mark();
+            fluent_method(value)
+        });
+
+        recorded.map(move |()| builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod stateless {
+        use recorded::*;
+
+        #[test]
+        fn default_fluent_stack() {
+            let (result, trace) = Recorded::new(FluentBuilder::<String, Stack>::default())
+                .fluent_mut(|v| v.push_str("_f1"))
+                .label("f1")
+                .fluent(|v| format!("{}_f2", v))
+                .into_value_traced(|| "default".to_owned());
+
+            assert_eq!("default_f1_f2", result);
+
+            assert_eq!(&TraceBase::Default, trace.base());
+            assert_eq!(2, trace.steps().len());
+
+            assert_eq!(Call::FluentMut, trace.steps()[0].call());
+            assert_eq!(Some("f1"), trace.steps()[0].label());
+            assert_eq!(file!(), trace.steps()[0].location().file());
+
+            assert_eq!(Call::Fluent, trace.steps()[1].call());
+            assert_eq!(None, trace.steps()[1].label());
+        }
+
+        #[test]
+        fn default_fluent_label_inner() {
+            let builder = Recorded::new(FluentBuilder::<String, Stack>::default())
+                .fluent_mut(|_| panic!("failed at f1"))
+                .label("f1")
+                .into_inner();
+
+            let err = builder
+                .into_value_catch_unwind(|| "default".to_owned())
+                .unwrap_err();
+
+            assert_eq!(Some("f1"), err.label());
+        }
+
+        #[test]
+        fn value_fluent_stack_boxed() {
+            let (result, trace) = Recorded::new(FluentBuilder::<String, Stack>::default())
+                .value("value".to_owned())
+                .label("value")
+                .fluent_mut(|v| v.push_str("_f1"))
+                .boxed()
+                .into_value_traced(|| "default".to_owned());
+
+            assert_eq!("value_f1", result);
+
+            match *trace.base() {
+                TraceBase::Value(ref step) => assert_eq!(Some("value"), step.label()),
+                ref base => panic!("unexpected base {:?}", base),
+            }
+
+            assert_eq!(1, trace.steps().len());
+        }
+
+        #[test]
+        fn value_fluent_override() {
+            let (result, trace) = Recorded::new(FluentBuilder::<String>::default())
+                .value("value".to_owned())
+                .fluent_mut(|v| v.push_str("_f1"))
+                .label("f1")
+                .fluent_mut(|v| v.push_str("_f2"))
+                .label("f2")
+                .into_value_traced(|| "default".to_owned());
+
+            assert_eq!("default_f2", result);
+
+            assert_eq!(&TraceBase::Default, trace.base());
+            assert_eq!(1, trace.steps().len());
+            assert_eq!(Some("f2"), trace.steps()[0].label());
+        }
+    }
+
+    mod stateful {
+        use recorded::*;
+
+        #[test]
+        fn from_seed_fluent_stack() {
+            let (result, trace) = Recorded::new(
+                StatefulFluentBuilder::<&str, String, Stack>::from_seed("seed"),
+            )
+            .fluent_mut("_f1", |s, v| v.push_str(s))
+            .fluent("_f2", |s, v| format!("{}{}", v, s))
+            .shared()
+            .into_value_traced(|seed| seed.to_owned());
+
+            assert_eq!("seed_f1_f2", result);
+
+            assert_eq!(&TraceBase::Seed, trace.base());
+            assert_eq!(
+                vec![Call::FluentMut, Call::Fluent],
+                trace.steps().iter().map(|s| s.call()).collect::<Vec<_>>()
+            );
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use imp::Storage;
+use {Boxed, DefaultStack, FluentBuilder, Shared, StackMode};
+
+/**
+A fluent builder that takes self by-reference instead of by-value.
+
+The `RefBuilder` is useful for values that are themselves configured through methods that take `&mut self`,
+like `std::process::Command`.
+Fluent methods are stored in `Boxed` or `Shared` storage, so the type of the builder doesn't change as methods are added to it.
+A `Shared` builder can be sent to other threads.
+
+# Examples
+
+```
+use std::process::Command;
+use fluent_builder::{RefBuilder, Stack};
+
+let mut builder = RefBuilder::<Command, Stack>::new();
+
+builder
+    .fluent(|cmd| cmd.arg("-l"))
+    .fluent(|cmd| cmd.arg("-a"));
+
+let cmd = builder.into_value(|| Command::new("ls"));
+
+assert_eq!(vec!["-l", "-a"], cmd.get_args().collect::<Vec<_>>());
+```
+*/
+#[must_use = "builders do nothing unless they're converted into a value"]
+pub struct RefBuilder<TValue, TStack = DefaultStack, TStorage = Boxed>
+where
+    TStorage: Storage<TValue>,
+{
+    inner: FluentBuilder<TValue, TStack, TStorage>,
+}
+
+impl<TValue, TStack> Default for RefBuilder<TValue, TStack, Boxed> {
+    fn default() -> Self {
+        RefBuilder {
+            inner: FluentBuilder::default(),
+        }
+    }
+}
+
+impl<TValue, TStack> Default for RefBuilder<TValue, TStack, Shared> {
+    fn default() -> Self {
+        RefBuilder {
+            inner: FluentBuilder::default(),
+        }
+    }
+}
+
+impl<TValue, TStack> From<FluentBuilder<TValue, TStack, Boxed>>
+    for RefBuilder<TValue, TStack, Boxed>
+{
+    fn from(inner: FluentBuilder<TValue, TStack, Boxed>) -> Self {
+        RefBuilder { inner }
+    }
+}
+
+impl<TValue, TStack> From<FluentBuilder<TValue, TStack, Shared>>
+    for RefBuilder<TValue, TStack, Shared>
+{
+    fn from(inner: FluentBuilder<TValue, TStack, Shared>) -> Self {
+        RefBuilder { inner }
+    }
+}
+
+impl<TValue, TStack, TStorage> RefBuilder<TValue, TStack, TStorage>
+where
+    Self: Default,
+    TStorage: Storage<TValue>,
+{
+    /**
+    Create a default `RefBuilder`.
+    */
+    pub fn new() -> Self {
+        RefBuilder::default()
+    }
+
+    /**
+    Set a value on the builder.
+
+    This will override any contained state.
+    That means if the builder currently contains fluent methods then those methods will be discarded.
+    */
+    pub fn value(&mut self, value: TValue) -> &mut Self {
+        self.inner.set_value(value);
+        self
+    }
+
+    /**
+    Convert the builder into a value.
+
+    This has the same behaviour as `FluentBuilder.into_value`.
+    */
+    pub fn into_value<TDefault>(self, default_value: TDefault) -> TValue
+    where
+        TDefault: FnOnce() -> TValue,
+    {
+        self.inner.into_value(default_value)
+    }
+
+    /**
+    Convert the builder into a value, using `Default` to construct a missing value.
+
+    This has the same behaviour as `FluentBuilder.into_default_value`.
+    */
+    pub fn into_default_value(self) -> TValue
+    where
+        TValue: Default,
+    {
+        self.inner.into_default_value()
+    }
+
+    /**
+    Apply the builder to an existing value.
+
+    This has the same behaviour as `FluentBuilder.apply_to`.
+    */
+    pub fn apply_to(self, target: &mut TValue) {
+        self.inner.apply_to(target)
+    }
+
+    /**
+    Apply the builder to an existing value, using a function to construct a fallback for the target.
+
+    This has the same behaviour as `FluentBuilder.apply_to_or_else`.
+    */
+    pub fn apply_to_or_else<TDefault>(self, target: &mut TValue, default_value: TDefault)
+    where
+        TDefault: FnOnce() -> TValue,
+    {
+        self.inner.apply_to_or_else(target, default_value)
+    }
+
+    /**
+    Get the inner fluent builder.
+    */
+    pub fn into_inner(self) -> FluentBuilder<TValue, TStack, TStorage> {
+        self.inner
+    }
+}
+
+impl<TValue, TStack> RefBuilder<TValue, TStack, Boxed>
+where
+    TValue: 'static,
+    TStack: StackMode,
+{
+    /**
+    Add a fluent method to the builder.
+
+    This has the same behaviour as `FluentBuilder.fluent_ref`.
+    */
+    #[track_caller]
+    pub fn fluent<TNextMethod>(&mut self, fluent_method: TNextMethod) -> &mut Self
+    where
+        TNextMethod: FnOnce(&mut TValue) -> &mut TValue + 'static,
+    {
+        self.inner.push_fluent_mut(move |value| {
+            fluent_method(value);
+        });
+        self
+    }
+}
+
+impl<TValue, TStack> RefBuilder<TValue, TStack, Shared>
+where
+    TValue: Send + 'static,
+    TStack: StackMode,
+{
+    /**
+    Add a fluent method to the builder.
+
+    This has the same behaviour as the `Boxed` builder's `fluent`, but requires the method is `Send`.
+    */
+    #[track_caller]
+    pub fn fluent<TNextMethod>(&mut self, fluent_method: TNextMethod) -> &mut Self
+    where
+        TNextMethod: FnOnce(&mut TValue) -> &mut TValue + Send + 'static,
+    {
+        self.inner.push_fluent_mut(move |value| {
+            fluent_method(value);
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod fluent_override {
+        use ref_builder::*;
+
+        #[test]
+        fn default_fluent() {
+            let mut builder = RefBuilder::<String>::new();
+
+            builder
+                .fluent(|v| {
+                    v.push_str("_f1");
+                    v
+                })
+                .fluent(|v| {
+                    v.push_str("_f2");
+                    v
+                });
+
+            let result = builder.into_value(|| "default".to_owned());
+
+            assert_eq!("default_f2", result);
+        }
+    }
+
+    mod fluent_stack {
+        use ref_builder::*;
+        use Stack;
+
+        #[test]
+        fn default_fluent() {
+            let mut builder = RefBuilder::<String, Stack>::new();
+
+            builder
+                .fluent(|v| {
+                    v.push_str("_f1");
+                    v
+                })
+                .fluent(|v| {
+                    v.push_str("_f2");
+                    v
+                });
+
+            let result = builder.into_value(|| "default".to_owned());
+
+            assert_eq!("default_f1_f2", result);
+        }
+
+        #[test]
+        fn value_fluent_apply_to() {
+            let mut builder = RefBuilder::<String, Stack>::new();
+
+            builder.value("value".to_owned()).fluent(|v| {
+                v.push_str("_f1");
+                v
+            });
+
+            let mut target = "target".to_owned();
+            builder.apply_to(&mut target);
+
+            assert_eq!("value_f1", target);
+        }
+
+        #[test]
+        fn default_fluent_shared() {
+            use std::thread;
+
+            let mut builder = RefBuilder::<String, Stack, Shared>::new();
+
+            builder.fluent(|v| {
+                v.push_str("_f1");
+                v
+            });
+
+            let result = thread::spawn(move || {
+                builder.fluent(|v| {
+                    v.push_str("_f2");
+                    v
+                });
+
+                builder.into_value(|| "default".to_owned())
+            })
+            .join()
+            .unwrap();
+
+            assert_eq!("default_f1_f2", result);
+        }
+    }
+}
@@ -0,0 +1,323 @@
+use std::any::{self, Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use {SharedFluentBuilder, Stack};
+
+/**
+A collection of stacking builders, one for each type of value.
+
+Different parts of an application can configure a value without having access to the builder for it.
+Each type can be given a default constructor using `register`, and fluent methods using `configure`.
+The value is built later using `build`, which applies all of the configured methods over the default.
+
+Builders use `Shared` storage, so the registry can be configured from multiple threads.
+
+# Examples
+
+```
+use fluent_builder::BuilderRegistry;
+
+struct HttpConfig {
+    port: u16,
+    middleware: Vec<&'static str>,
+}
+
+let registry = BuilderRegistry::new();
+
+registry.register(|| HttpConfig {
+    port: 80,
+    middleware: Vec::new(),
+});
+
+// Plugins can configure the value during startup
+registry.configure::<HttpConfig>(|c| c.middleware.push("logging"));
+registry.configure::<HttpConfig>(|c| c.port = 8080);
+
+let config = registry.build::<HttpConfig>();
+
+assert_eq!(8080, config.port);
+assert_eq!(vec!["logging"], config.middleware);
+```
+*/
+#[derive(Default)]
+pub struct BuilderRegistry {
+    entries: Mutex<HashMap<TypeId, Box<dyn Any + Send>>>,
+}
+
+struct Entry<TValue> {
+    builder: SharedFluentBuilder<TValue, Stack>,
+    default_value: Option<Arc<dyn Fn() -> TValue + Send + Sync>>,
+}
+
+impl BuilderRegistry {
+    /**
+    Create an empty registry.
+    */
+    pub fn new() -> Self {
+        BuilderRegistry::default()
+    }
+
+    /**
+    Register the function used to construct a default value for `TValue`.
+
+    Any previously registered default is replaced.
+    Fluent methods that have already been configured are retained.
+    */
+    pub fn register<TValue, TDefault>(&self, default_value: TDefault)
+    where
+        TValue: Send + 'static,
+        TDefault: Fn() -> TValue + Send + Sync + 'static,
+    {
+        let mut entries = self.lock();
+
+        entry::<TValue>(&mut entries).default_value = Some(Arc::new(default_value));
+    }
+
+    /**
+    Whether a default has been registered for `TValue`.
+    */
+    pub fn is_registered<TValue>(&self) -> bool
+    where
+        TValue: Send + 'static,
+    {
+        self.lock()
+            .get(&TypeId::of::<TValue>())
+            .and_then(|entry| entry.downcast_ref::<Entry<TValue>>())
+            .is_some_and(|entry| entry.default_value.is_some())
+    }
+
+    /**
+    Stack a fluent method on the builder for `TValue`.
+
+    The method is applied when the value is built, after any methods that were configured before it.
+    The type of value can be given explicitly, like `configure::<TValue>(|value| ...)`, instead of annotating the method's argument.
+    */
+    #[track_caller]
+    pub fn configure<TValue>(&self, fluent_method: impl FnOnce(&mut TValue) + Send + 'static)
+    where
+        TValue: Send + 'static,
+    {
+        let mut entries = self.lock();
+
+        entry::<TValue>(&mut entries)
+            .builder
+            .push_fluent_mut(fluent_method);
+    }
+
+    /**
+    Build a value for `TValue`, applying the configured fluent methods over its registered default.
+
+    The configured methods are consumed, so building the same type again will only use the default,
+    plus any methods configured in between.
+    The registry isn't locked while the value is built, so fluent methods can use the registry themselves.
+
+    # Panics
+
+    This method will panic if no default has been registered for `TValue`.
+    Any configured methods are kept, so the value can still be built after a default is registered.
+    */
+    pub fn build<TValue>(&self) -> TValue
+    where
+        TValue: Send + 'static,
+    {
+        match self.try_build() {
+            Some(value) => value,
+            None => panic!(
+                "no default is registered for `{}` (use `register` to add one)",
+                any::type_name::<TValue>()
+            ),
+        }
+    }
+
+    /**
+    Attempt to build a value for `TValue`.
+
+    This method behaves the same as `build`, but returns `None` if no default has been registered for `TValue`.
+    In that case any configured methods are left in the registry.
+    */
+    pub fn try_build<TValue>(&self) -> Option<TValue>
+    where
+        TValue: Send + 'static,
+    {
+        let (builder, default_value) = {
+            let mut entries = self.lock();
+            let entry = entries
+                .get_mut(&TypeId::of::<TValue>())?
+                .downcast_mut::<Entry<TValue>>()
+                .expect("the registry entry has a different type");
+
+            let default_value = entry.default_value.clone()?;
+
+            (entry.builder.take(), default_value)
+        };
+
+        Some(builder.into_value(move || default_value()))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<TypeId, Box<dyn Any + Send>>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn entry<TValue>(entries: &mut HashMap<TypeId, Box<dyn Any + Send>>) -> &mut Entry<TValue>
+where
+    TValue: Send + 'static,
+{
+    entries
+        .entry(TypeId::of::<TValue>())
+        .or_insert_with(|| {
+            Box::new(Entry::<TValue> {
+                builder: SharedFluentBuilder::default(),
+                default_value: None,
+            })
+        })
+        .downcast_mut()
+        .expect("the registry entry has a different type")
+}
+
+impl<TValue> Drop for Entry<TValue> {
+    fn drop(&mut self) {
+        self.builder.take().discard();
+    }
+}
+
+impl fmt::Debug for BuilderRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BuilderRegistry")
+            .field("types", &self.lock().len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+    use std::thread;
+
+    use registry::*;
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Config {
+        plugins: Vec<u32>,
+    }
+
+    #[test]
+    fn configure_build() {
+        let registry = BuilderRegistry::new();
+
+        registry.configure(|c: &mut String| c.push_str("_f1"));
+        registry.register(|| "default".to_owned());
+        registry.configure(|c: &mut String| c.push_str("_f2"));
+        registry.register(|| 1);
+
+        assert!(registry.is_registered::<String>());
+        assert!(!registry.is_registered::<Config>());
+
+        assert_eq!("default_f1_f2", registry.build::<String>());
+        assert_eq!("default", registry.build::<String>());
+        assert_eq!(1, registry.build::<i32>());
+    }
+
+    #[test]
+    fn configure_turbofish() {
+        let registry = BuilderRegistry::new();
+
+        registry.register(Config::default);
+        registry.configure::<Config>(|c| c.plugins.push(1));
+        registry.configure::<Config>(|c| c.plugins.push(2));
+
+        assert_eq!(vec![1, 2], registry.build::<Config>().plugins);
+    }
+
+    #[test]
+    fn configure_build_across_threads() {
+        let registry = BuilderRegistry::new();
+
+        registry.register(|| Config {
+            plugins: Vec::new(),
+        });
+
+        thread::scope(|scope| {
+            for plugin in 0..4 {
+                let registry = &registry;
+                scope.spawn(move || {
+                    registry.configure(move |c: &mut Config| c.plugins.push(plugin))
+                });
+            }
+        });
+
+        let mut config = registry.build::<Config>();
+        config.plugins.sort();
+
+        assert_eq!(vec![0, 1, 2, 3], config.plugins);
+    }
+
+    #[test]
+    fn configure_many() {
+        let registry = BuilderRegistry::new();
+
+        registry.register(Config::default);
+        for plugin in 0..100_000 {
+            registry.configure(move |c: &mut Config| c.plugins.push(plugin));
+        }
+
+        assert_eq!(100_000, registry.build::<Config>().plugins.len());
+
+        registry.configure(|c: &mut Config| c.plugins.push(0));
+
+        drop(registry);
+    }
+
+    #[test]
+    fn configure_drop_unbuilt() {
+        let registry = BuilderRegistry::new();
+
+        registry.configure(|c: &mut String| c.push_str("_f1"));
+
+        drop(registry);
+    }
+
+    #[test]
+    fn try_build_unregistered() {
+        let registry = BuilderRegistry::new();
+
+        assert_eq!(None, registry.try_build::<String>());
+
+        registry.configure(|c: &mut String| c.push_str("_f1"));
+
+        assert_eq!(None, registry.try_build::<String>());
+
+        registry.register(|| "default".to_owned());
+
+        assert_eq!(
+            Some("default_f1".to_owned()),
+            registry.try_build::<String>()
+        );
+    }
+
+    #[test]
+    fn build_unregistered_keeps_methods() {
+        let registry = BuilderRegistry::new();
+
+        registry.configure(|c: &mut String| c.push_str("_f1"));
+
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| registry.build::<String>()));
+        assert!(result.is_err());
+
+        registry.register(|| "default".to_owned());
+
+        assert_eq!("default_f1", registry.build::<String>());
+    }
+
+    #[test]
+    #[should_panic(expected = "no default is registered")]
+    fn build_unregistered() {
+        let registry = BuilderRegistry::new();
+
+        registry.configure(|c: &mut String| c.push_str("_f1"));
+
+        registry.build::<String>();
+    }
+}